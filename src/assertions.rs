@@ -0,0 +1,109 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
+use sui_sdk::types::parse_sui_struct_tag;
+
+use crate::created;
+
+/// The execution status a transaction is expected to end with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExpectedStatus {
+    Success,
+    Failure,
+}
+
+/// Objects of a type a transaction is expected to create, written `<type>` for at least
+/// one object or `<type>=<count>` for exactly `count` objects.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreatedType {
+    pub object_type: StructTag,
+    /// Exact number of objects, or `None` for at least one.
+    pub count: Option<usize>,
+}
+
+impl FromStr for CreatedType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (object_type, count) = match s.rsplit_once('=') {
+            Some((object_type, count)) => (
+                object_type,
+                Some(
+                    count
+                        .trim()
+                        .parse()
+                        .map_err(|e| anyhow!("Invalid count `{count}`: {e}"))?,
+                ),
+            ),
+            None => (s, None),
+        };
+        Ok(Self {
+            object_type: parse_sui_struct_tag(object_type.trim())?,
+            count,
+        })
+    }
+}
+
+/// Conditions an executed transaction must meet, all of which are checked together.
+#[derive(Clone, Debug, Default)]
+pub struct Assertions {
+    pub status: Option<ExpectedStatus>,
+    pub created: Vec<CreatedType>,
+}
+
+impl Assertions {
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none() && self.created.is_empty()
+    }
+
+    /// A description of each condition the transaction does not meet.
+    pub fn failures(&self, response: &SuiTransactionBlockResponse) -> Vec<String> {
+        let mut failures = vec![];
+        if let Some(expected) = self.status {
+            let status = response.effects.as_ref().map(|effects| effects.status());
+            let met = match (expected, status) {
+                (ExpectedStatus::Success, Some(SuiExecutionStatus::Success)) => true,
+                (ExpectedStatus::Failure, Some(SuiExecutionStatus::Failure { .. })) => true,
+                _ => false,
+            };
+            if !met {
+                let found = status
+                    .map(|status| format!("{status:?}"))
+                    .unwrap_or_else(|| "no effects".to_string());
+                failures.push(format!("expected status {expected:?}, found {found}"));
+            }
+        }
+        for expected in &self.created {
+            let created = created::created_ids(response, Some(&expected.object_type)).len();
+            match expected.count {
+                Some(count) if created != count => failures.push(format!(
+                    "expected {count} created {}, found {created}",
+                    expected.object_type
+                )),
+                None if created == 0 => {
+                    failures.push(format!("expected a created {}", expected.object_type))
+                }
+                _ => {}
+            }
+        }
+        failures
+    }
+
+    /// Fail with every unmet condition if the transaction does not meet all of them.
+    pub fn check(&self, response: &SuiTransactionBlockResponse) -> Result<(), anyhow::Error> {
+        let failures = self.failures(response);
+        if failures.is_empty() {
+            return Ok(());
+        }
+        bail!(
+            "Transaction {} failed {} assertions:\n  {}",
+            response.digest,
+            failures.len(),
+            failures.join("\n  ")
+        )
+    }
+}
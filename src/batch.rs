@@ -0,0 +1,162 @@
+use std::{fs, path::Path};
+
+use anyhow::Context;
+use sui_json_rpc_types::SuiTransactionBlockResponse;
+use sui_sdk::types::base_types::ObjectID;
+
+use crate::client::{Action, GameClient};
+
+/// Read a list of card IDs, one per line. Blank lines and lines starting with `#` are skipped.
+pub fn read_card_ids(path: &Path) -> Result<Vec<ObjectID>, anyhow::Error> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?;
+    parse_object_ids(&contents)
+}
+
+/// Parse object IDs written one per line, skipping blank lines and `#` comments.
+pub fn parse_object_ids(contents: &str) -> Result<Vec<ObjectID>, anyhow::Error> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            ObjectID::from_hex_literal(line).with_context(|| format!("Invalid object ID `{line}`"))
+        })
+        .collect()
+}
+
+/// Pick `sample` indexes spread evenly across a batch of `len` operations.
+fn sample_indexes(len: usize, sample: usize) -> Vec<usize> {
+    let sample = sample.clamp(1, len.max(1));
+    (0..sample).map(|i| i * len / sample).collect()
+}
+
+/// The dry run of one operation of a batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperationEstimate {
+    /// Position of the operation in the batch, from 0.
+    pub index: usize,
+    pub card: ObjectID,
+    /// Net cost of the operation in MIST.
+    pub net_cost: i64,
+    /// `None` if the operation would succeed, otherwise the execution error.
+    pub error: Option<String>,
+}
+
+/// Projected gas of a batch, from dry runs of all of its operations or of a sample of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchEstimate {
+    /// Number of operations in the batch.
+    pub operations: usize,
+    /// The operations that were dry-run, in batch order.
+    pub dry_runs: Vec<OperationEstimate>,
+}
+
+impl BatchEstimate {
+    /// Whether only some of the operations were dry-run.
+    pub fn is_sampled(&self) -> bool {
+        self.dry_runs.len() < self.operations
+    }
+
+    /// Average net cost of the dry-run operations, or `None` if none were.
+    pub fn average_net_cost(&self) -> Option<i64> {
+        (!self.dry_runs.is_empty()).then(|| self.dry_run_cost() / self.dry_runs.len() as i64)
+    }
+
+    /// Total net cost of the batch: the sum of the dry runs, or their average extrapolated to
+    /// every operation when they are a sample.
+    pub fn projected_total(&self) -> i64 {
+        match self.average_net_cost() {
+            Some(average) if self.is_sampled() => average * self.operations as i64,
+            _ => self.dry_run_cost(),
+        }
+    }
+
+    /// Number of dry-run operations that would fail.
+    pub fn failures(&self) -> usize {
+        self.dry_runs
+            .iter()
+            .filter(|dry_run| dry_run.error.is_some())
+            .count()
+    }
+
+    fn dry_run_cost(&self) -> i64 {
+        self.dry_runs.iter().map(|dry_run| dry_run.net_cost).sum()
+    }
+}
+
+/// Dry-run the `create_room` operations of a batch and project its total gas.
+///
+/// With `sample`, only that many operations (spread evenly across the batch) are dry-run
+/// and their average cost is extrapolated to the whole batch. Nothing is executed. Each
+/// operation is dry-run with the configured budget, so one that would fail is reported
+/// rather than stopping the estimate.
+pub async fn estimate_create_rooms(
+    client: &GameClient,
+    cards: &[ObjectID],
+    sample: Option<usize>,
+) -> Result<BatchEstimate, anyhow::Error> {
+    let indexes = match sample {
+        Some(_) if cards.is_empty() => vec![],
+        Some(sample) => sample_indexes(cards.len(), sample),
+        None => (0..cards.len()).collect(),
+    };
+
+    let mut dry_runs = Vec::with_capacity(indexes.len());
+    for index in indexes {
+        let card = cards[index];
+        let plan = client
+            .plan_without_estimate(&Action::CreateRoom {
+                card,
+                extra_objects: vec![],
+                room_owner: None,
+            })
+            .await?;
+        let estimate = client.dry_run_plan(&plan).await?;
+        dry_runs.push(OperationEstimate {
+            index,
+            card,
+            net_cost: estimate.net_cost(),
+            error: estimate.error,
+        });
+    }
+    Ok(BatchEstimate {
+        operations: cards.len(),
+        dry_runs,
+    })
+}
+
+/// A batch that stopped at a failed transaction, with the responses of the transactions
+/// executed before it.
+#[derive(Debug, thiserror::Error)]
+#[error("The batch stopped after {} transactions: {error:#}", responses.len())]
+pub struct BatchFailed {
+    pub responses: Vec<SuiTransactionBlockResponse>,
+    pub error: anyhow::Error,
+}
+
+/// Execute one `create_room` transaction per card, in order, and return their responses.
+///
+/// The batch stops at the first card whose transaction cannot be executed. The transactions
+/// before it have landed all the same, so their responses are returned with the error.
+pub async fn create_rooms(
+    client: &GameClient,
+    cards: &[ObjectID],
+) -> Result<Vec<SuiTransactionBlockResponse>, BatchFailed> {
+    let mut responses = Vec::with_capacity(cards.len());
+    for &card in cards {
+        // The gas coin changes version after every transaction, so every plan looks it up again
+        let result = client
+            .execute(&Action::CreateRoom {
+                card,
+                extra_objects: vec![],
+                room_owner: None,
+            })
+            .await;
+        match result {
+            Ok(response) => responses.push(response),
+            Err(error) => return Err(BatchFailed { responses, error }),
+        }
+    }
+    Ok(responses)
+}
@@ -0,0 +1,774 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use move_core_types::language_storage::StructTag;
+use serde::Serialize;
+use serde_json::{json, Value};
+use sui_json_rpc_types::{
+    Balance, Coin, SuiExecutionStatus, SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions,
+    SuiObjectResponseQuery, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_sdk::{
+    types::{
+        base_types::{ObjectID, ObjectRef, SuiAddress},
+        digests::TransactionDigest,
+        transaction::{
+            ProgrammableTransaction, SenderSignedData, TransactionDataAPI, TransactionKind,
+        },
+        Identifier,
+    },
+    SuiClient,
+};
+use tokio::sync::OnceCell;
+use tracing::{field, info_span, instrument, warn, Instrument, Span};
+
+use crate::{
+    error::GameError,
+    game::{self, CardAssignment, Deployment, KeyScheme},
+    gas::{self, GasEstimate, GasSettings},
+    gas_pool::GasPool,
+    modules::ModuleCache,
+    plan::TransactionPlan,
+    retry::{self, RpcPolicies},
+    transcript::Transcript,
+};
+
+/// An operation on the `gamecards` package that can be planned and executed.
+#[derive(Clone, Debug)]
+pub enum Action {
+    /// Call `create_room` with the deployment's room, a single game card and any extra owned
+    /// objects, optionally transferring an owned room to `room_owner`.
+    CreateRoom {
+        card: ObjectID,
+        /// Objects owned by the sender, passed after the cards in order.
+        extra_objects: Vec<ObjectID>,
+        room_owner: Option<SuiAddress>,
+    },
+    /// Transfer each card to its recipient in a single transaction.
+    DistributeCards { assignments: Vec<CardAssignment> },
+    /// Send an amount of MIST to a recipient, taken from the sender's coins other than the
+    /// gas coin when they hold enough.
+    TransferSui { recipient: SuiAddress, amount: u64 },
+    /// Call `gamecards::<function>` with a shared room, such as a function that joins it.
+    JoinRoom { room: ObjectID, function: String },
+}
+
+/// Plans and executes `gamecards` transactions on behalf of a single sender.
+///
+/// Planning and execution are separate steps: [`GameClient::plan`] resolves every
+/// object, the gas coin and the gas price into a [`TransactionPlan`] without signing
+/// anything, and [`GameClient::execute_plan`] signs and submits a (possibly modified) plan.
+///
+/// A client built with [`GameClient::read_only`] has no sender and never loads a keystore;
+/// it can only be used for queries.
+pub struct GameClient {
+    sui: SuiClient,
+    sender: Option<SuiAddress>,
+    deployment: Deployment,
+    gas: GasSettings,
+    key_scheme: Option<KeyScheme>,
+    confirm_timeout: Duration,
+    rpc: RpcPolicies,
+    type_check: bool,
+    gas_pool: Option<GasPool>,
+    retry_congestion: bool,
+    /// The protocol's `max_tx_gas`, fetched once per client.
+    max_tx_gas: OnceCell<u64>,
+    transcript: Option<Transcript>,
+    /// Coin type that pays for gas instead of SUI.
+    gas_coin_type: Option<StructTag>,
+    /// Budgets above this many MIST are refused unless `allow_high_budget` is set.
+    max_budget: Option<u64>,
+    allow_high_budget: bool,
+    /// The Move modules looked up while planning, shared by every plan of the client.
+    modules: ModuleCache,
+}
+
+impl GameClient {
+    pub fn new(
+        sui: SuiClient,
+        sender: SuiAddress,
+        deployment: Deployment,
+        gas: GasSettings,
+    ) -> Self {
+        Self::build(sui, Some(sender), deployment, gas)
+    }
+
+    /// A client for queries only, which has no sender and never touches the keystore.
+    pub fn read_only(sui: SuiClient, deployment: Deployment) -> Self {
+        let gas = GasSettings {
+            budget: gas::DEFAULT_GAS_BUDGET,
+            price_margin_pct: 0,
+            estimate_basis: None,
+        };
+        Self::build(sui, None, deployment, gas)
+    }
+
+    fn build(
+        sui: SuiClient,
+        sender: Option<SuiAddress>,
+        deployment: Deployment,
+        gas: GasSettings,
+    ) -> Self {
+        Self {
+            modules: ModuleCache::new(sui.clone()),
+            sui,
+            sender,
+            deployment,
+            gas,
+            key_scheme: None,
+            confirm_timeout: game::DEFAULT_CONFIRM_TIMEOUT,
+            rpc: RpcPolicies::default(),
+            type_check: true,
+            gas_pool: None,
+            retry_congestion: false,
+            max_tx_gas: OnceCell::new(),
+            transcript: None,
+            gas_coin_type: None,
+            max_budget: None,
+            allow_high_budget: false,
+        }
+    }
+
+    /// Only sign with the sender's key if it uses this scheme.
+    pub fn with_key_scheme(mut self, key_scheme: Option<KeyScheme>) -> Self {
+        self.key_scheme = key_scheme;
+        self
+    }
+
+    /// How long to keep looking for a submitted transaction when the full node times out
+    /// waiting for its local execution.
+    pub fn with_confirm_timeout(mut self, confirm_timeout: Duration) -> Self {
+        self.confirm_timeout = confirm_timeout;
+        self
+    }
+
+    /// Retry policies for reads and for transaction submission.
+    pub fn with_rpc_policies(mut self, rpc: RpcPolicies) -> Self {
+        self.rpc = rpc;
+        self
+    }
+
+    /// Whether objects passed to Move calls are checked to have the expected type
+    /// before the transaction is built; enabled by default.
+    pub fn with_type_check(mut self, type_check: bool) -> Self {
+        self.type_check = type_check;
+        self
+    }
+
+    /// Whether [`GameClient::execute`] plans and submits an action again, under the
+    /// congestion policy, when a shared object is congested; disabled by default.
+    pub fn with_congestion_retry(mut self, retry_congestion: bool) -> Self {
+        self.retry_congestion = retry_congestion;
+        self
+    }
+
+    /// Pay for gas with coins of this type instead of SUI, on a chain that accepts them.
+    ///
+    /// Every plan is then dry-run before it is returned, so a chain that refuses the coin
+    /// as gas fails the plan instead of the submission. SUI is the default, and naming it
+    /// here is the same as passing `None`. A gas pool must hold coins of the same type.
+    pub fn with_gas_coin_type(mut self, coin_type: Option<StructTag>) -> Self {
+        self.gas_coin_type = gas::non_sui_gas_type(coin_type);
+        self
+    }
+
+    /// Pay for gas with the coins of a pool instead of selecting a coin for each plan.
+    pub fn with_gas_pool(mut self, gas_pool: GasPool) -> Self {
+        self.gas_pool = Some(gas_pool);
+        self
+    }
+
+    /// Refuse budgets above `max_budget` MIST, or only warn about them if
+    /// `allow_high_budget` is set, see [`gas::check_budget`]. This applies to the budget in
+    /// the gas settings and to every budget estimated from a dry run.
+    pub fn with_budget_limit(mut self, max_budget: Option<u64>, allow_high_budget: bool) -> Self {
+        self.max_budget = max_budget;
+        self.allow_high_budget = allow_high_budget;
+        self
+    }
+
+    /// Record the client's RPC calls and gas decisions to a transcript.
+    pub fn with_transcript(mut self, transcript: Option<Transcript>) -> Self {
+        self.transcript = transcript;
+        self
+    }
+
+    fn record_rpc<T: Serialize>(
+        &self,
+        method: &str,
+        params: impl FnOnce() -> Value,
+        result: Result<T, &anyhow::Error>,
+    ) {
+        if let Some(transcript) = &self.transcript {
+            transcript.rpc(method, params(), result);
+        }
+    }
+
+    fn record_decision(&self, name: &str, details: impl FnOnce() -> Value) {
+        if let Some(transcript) = &self.transcript {
+            transcript.decision(name, details());
+        }
+    }
+
+    pub fn sui(&self) -> &SuiClient {
+        &self.sui
+    }
+
+    /// The sender of planned transactions, or `None` for a read-only client.
+    pub fn sender(&self) -> Option<SuiAddress> {
+        self.sender
+    }
+
+    fn signer(&self) -> Result<SuiAddress, anyhow::Error> {
+        self.sender
+            .ok_or_else(|| anyhow!("This client is read-only and cannot build transactions"))
+    }
+
+    /// The cache of the Move modules the client looks up, such as the signatures of the
+    /// functions it calls.
+    pub fn modules(&self) -> &ModuleCache {
+        &self.modules
+    }
+
+    pub fn deployment(&self) -> &Deployment {
+        &self.deployment
+    }
+
+    pub fn gas(&self) -> &GasSettings {
+        &self.gas
+    }
+
+    /// The coin type that pays for gas, or `None` for SUI.
+    pub fn gas_coin_type(&self) -> Option<&StructTag> {
+        self.gas_coin_type.as_ref()
+    }
+
+    /// The largest gas budget the network accepts, from its protocol config. It is fetched
+    /// on first use and then cached for the lifetime of the client.
+    pub async fn max_tx_gas(&self) -> Result<u64, anyhow::Error> {
+        self.max_tx_gas
+            .get_or_try_init(|| async {
+                let result = self.rpc.read.run(|| gas::max_tx_gas(&self.sui)).await;
+                self.record_rpc("sui_getProtocolConfig", || json!({}), result.as_ref());
+                result
+            })
+            .await
+            .copied()
+    }
+
+    /// Resolve the inputs, commands, gas coin, budget and gas price of an action.
+    ///
+    /// Planning only reads from the network, so it is retried under the read policy. With
+    /// an estimate basis in the gas settings, or a gas coin type other than SUI, the plan is
+    /// then completed by [`GameClient::estimate`].
+    /// A configured budget above `max_tx_gas` is refused, since the network would reject it,
+    /// and so is any budget above the limit set with [`GameClient::with_budget_limit`].
+    #[instrument(skip_all)]
+    pub async fn plan(&self, action: &Action) -> Result<TransactionPlan, anyhow::Error> {
+        let plan = self.plan_without_estimate(action).await?;
+        self.estimate(plan).await
+    }
+
+    /// Like [`GameClient::plan`], but keep the configured budget and gas coin without a dry
+    /// run, for callers that change the transaction or dry-run it themselves, such as a
+    /// parameter sweep or a batch estimate.
+    pub async fn plan_without_estimate(
+        &self,
+        action: &Action,
+    ) -> Result<TransactionPlan, anyhow::Error> {
+        let sender = self.signer()?;
+        let max_tx_gas = self.max_tx_gas().await?;
+        gas::check_protocol_budget(self.gas.budget, max_tx_gas)?;
+        if let Some(max_budget) = self.max_budget {
+            gas::check_budget(self.gas.budget, max_budget, self.allow_high_budget)?;
+        }
+        // The objects, coins, modules and gas price fetched while planning go to the transcript
+        let planning = self.rpc.read.run(|| self.plan_once(sender, action));
+        let plan = Transcript::scope(self.transcript.as_ref(), planning).await?;
+        self.record_decision("plan", || {
+            json!({
+                "action": format!("{action:?}"),
+                "gas_coin": plan.gas_coin,
+                "gas_budget": plan.gas_budget,
+                "gas_price": plan.gas_price,
+            })
+        });
+        Ok(plan)
+    }
+
+    /// Dry-run a complete plan when the gas settings need it, and return it ready to sign.
+    ///
+    /// With an estimate basis, the budget is set from the gas summary of the dry run, capped
+    /// at the protocol's `max_tx_gas`; if that is more than the configured budget, a gas coin
+    /// that covers it is selected again. A plan that pays gas with another coin than SUI is
+    /// dry-run too, to check the network accepts it. A plan whose dry run fails is refused,
+    /// with the error of the dry run. Other plans are returned as they are.
+    pub async fn estimate(
+        &self,
+        mut plan: TransactionPlan,
+    ) -> Result<TransactionPlan, anyhow::Error> {
+        if self.gas.estimate_basis.is_none() && self.gas_coin_type.is_none() {
+            return Ok(plan);
+        }
+        let sender = plan.sender;
+        let max_tx_gas = self.max_tx_gas().await?;
+        let estimate = self
+            .dry_run_plan(&plan)
+            .await
+            .map_err(|e| match &self.gas_coin_type {
+                Some(coin_type) => {
+                    e.context(format!("The network did not accept {coin_type} as gas"))
+                }
+                None => e,
+            })?;
+        // A failed dry run stops early, so its gas summary would give too small a budget
+        if let Some(error) = &estimate.error {
+            bail!("The dry run of the plan failed: {error}");
+        }
+        if let Some(basis) = self.gas.estimate_basis {
+            plan.gas_budget = basis.budget(&estimate.summary).min(max_tx_gas);
+            if let Some(max_budget) = self.max_budget {
+                gas::check_budget(plan.gas_budget, max_budget, self.allow_high_budget)?;
+            }
+            // The gas coin was picked to cover the configured budget, which the estimate exceeds
+            if plan.gas_budget > self.gas.budget {
+                let required = plan.gas_budget.saturating_add(plan.split_from_gas());
+                let gas_coin = self.gas_coin(sender, required).await?;
+                if gas_coin.0 != plan.gas_coin.0 && plan.uses_object(gas_coin.0) {
+                    bail!(
+                        "Coin {} of {sender}, selected to cover the estimated budget of {}, is \
+                         already an input of the transaction",
+                        gas_coin.0,
+                        gas::format_gas(required, self.gas_coin_type.as_ref())
+                    );
+                }
+                plan.gas_coin = gas_coin;
+            }
+            self.record_decision("estimated_gas_budget", || {
+                json!({
+                    "basis": format!("{basis:?}"),
+                    "summary": estimate.summary,
+                    "max_tx_gas": max_tx_gas,
+                    "gas_budget": plan.gas_budget,
+                })
+            });
+        }
+        Ok(plan)
+    }
+
+    async fn plan_once(
+        &self,
+        sender: SuiAddress,
+        action: &Action,
+    ) -> Result<TransactionPlan, anyhow::Error> {
+        let (pt, gas_coin) = match action {
+            Action::CreateRoom {
+                card,
+                extra_objects,
+                room_owner,
+            } => {
+                let request = game::CreateRoom {
+                    card: *card,
+                    extra_objects,
+                    room_owner: *room_owner,
+                    check_type: self.type_check,
+                };
+                let pt = game::create_room_ptb(
+                    &self.sui,
+                    &self.modules,
+                    &self.deployment,
+                    sender,
+                    request,
+                )
+                .await?;
+                (pt, self.gas_coin(sender, self.gas.budget).await?)
+            }
+            Action::DistributeCards { assignments } => {
+                let pt = game::distribute_cards_ptb(&self.sui, sender, assignments).await?;
+                (pt, self.gas_coin(sender, self.gas.budget).await?)
+            }
+            Action::TransferSui { recipient, amount } => {
+                self.transfer_sui(sender, *recipient, *amount).await?
+            }
+            Action::JoinRoom { room, function } => {
+                let pt = game::join_room_ptb(
+                    &self.sui,
+                    &self.modules,
+                    &self.deployment,
+                    *room,
+                    function,
+                )
+                .await?;
+                (pt, self.gas_coin(sender, self.gas.budget).await?)
+            }
+        };
+        let gas_price = gas::gas_price(&self.sui, self.gas.price_margin_pct).await?;
+        Ok(TransactionPlan::new(
+            sender,
+            pt,
+            gas_coin,
+            self.gas.budget,
+            gas_price,
+        ))
+    }
+
+    async fn gas_coin(
+        &self,
+        sender: SuiAddress,
+        required: u64,
+    ) -> Result<ObjectRef, anyhow::Error> {
+        let coin = match &self.gas_pool {
+            Some(pool) if pool.coin_type() != self.gas_coin_type.as_ref() => Err(anyhow!(
+                "The gas pool holds {} coins, but gas is paid in {}",
+                pool.coin_type().map_or("SUI".into(), ToString::to_string),
+                self.gas_coin_type
+                    .as_ref()
+                    .map_or("SUI".into(), ToString::to_string)
+            )),
+            Some(pool) => pool.coin(required),
+            None => game::gas_coin(&self.sui, sender, required, self.gas_coin_type.as_ref()).await,
+        }?;
+        self.record_decision("gas_coin", || {
+            json!({
+                "required": required,
+                "from_pool": self.gas_pool.is_some(),
+                "coin_type": self.gas_coin_type.as_ref().map(ToString::to_string),
+                "coin": coin,
+            })
+        });
+        Ok(coin)
+    }
+
+    /// The transfer and its gas coin. The amount comes from the sender's other coins, merged
+    /// if needed, so that the gas coin only has to cover the budget. If the other coins are
+    /// not enough, the amount is split from the gas coin instead.
+    async fn transfer_sui(
+        &self,
+        sender: SuiAddress,
+        recipient: SuiAddress,
+        amount: u64,
+    ) -> Result<(ProgrammableTransaction, ObjectRef), anyhow::Error> {
+        let gas_coin = self.gas_coin(sender, self.gas.budget).await?;
+        let pool_coins: Vec<_> = self
+            .gas_pool
+            .iter()
+            .flat_map(|pool| pool.coins())
+            .map(|coin| coin.coin_object_id)
+            .collect();
+        let mut coins = gas::sui_coins(&self.sui, sender).await?;
+        coins.retain(|coin| {
+            coin.coin_object_id != gas_coin.0 && !pool_coins.contains(&coin.coin_object_id)
+        });
+
+        match gas::select_coins(coins, amount) {
+            Some(source) => {
+                let source: Vec<_> = source.iter().map(Coin::object_ref).collect();
+                Ok((
+                    game::transfer_sui_ptb(recipient, amount, &source)?,
+                    gas_coin,
+                ))
+            }
+            None if self.gas_coin_type.is_some() => bail!(
+                "{sender} does not hold {} outside its gas coin, and gas is not paid in SUI",
+                gas::format_sui(amount)
+            ),
+            None => {
+                let gas_coin = self
+                    .gas_coin(sender, self.gas.budget.saturating_add(amount))
+                    .await?;
+                Ok((game::transfer_sui_ptb(recipient, amount, &[])?, gas_coin))
+            }
+        }
+    }
+
+    /// Dry-run a plan without signing or executing it.
+    pub async fn dry_run_plan(&self, plan: &TransactionPlan) -> Result<GasEstimate, anyhow::Error> {
+        let dry_run = gas::dry_run(&self.sui, plan.transaction_data());
+        Transcript::scope(self.transcript.as_ref(), dry_run).await
+    }
+
+    /// Sign a plan with the sender's key and submit it.
+    ///
+    /// The plan is signed once and the same signed transaction is re-submitted under the
+    /// write policy. Before each retry the transaction is looked up by its digest, so one
+    /// that landed despite a failed attempt is reported instead of being submitted again.
+    pub async fn execute_plan(
+        &self,
+        plan: TransactionPlan,
+    ) -> Result<SuiTransactionBlockResponse, anyhow::Error> {
+        let transaction = game::sign(plan.sender, plan.transaction_data(), self.key_scheme).await?;
+        let digest = *transaction.digest();
+        let split_from_gas = plan.split_from_gas();
+        // Tags the transaction span opened by `execute`, if this runs inside one
+        Span::current().record("digest", field::display(digest));
+
+        let mut retrying = false;
+        let response = self
+            .rpc
+            .write
+            .run(|| {
+                let check_landed = retrying;
+                retrying = true;
+                let transaction = transaction.clone();
+                async move {
+                    if check_landed {
+                        if let Ok(response) = self.fetch_transaction(digest).await {
+                            return Ok(response);
+                        }
+                    }
+                    // Polls for the confirmation of a timed out submission go to the transcript
+                    let execution = game::execute(&self.sui, transaction, self.confirm_timeout);
+                    Transcript::scope(self.transcript.as_ref(), execution).await
+                }
+            })
+            .await;
+        self.record_rpc(
+            "sui_executeTransactionBlock",
+            || json!({ "digest": digest, "transaction": plan.transaction_data() }),
+            response.as_ref(),
+        );
+        let response = response?;
+
+        if let (Some(pool), Some(effects)) = (&self.gas_pool, &response.effects) {
+            pool.update(
+                effects.gas_object().reference.to_object_ref(),
+                effects.gas_cost_summary(),
+                // A failed transaction is charged for gas, but splits nothing from the coin
+                if effects.status().is_ok() {
+                    split_from_gas
+                } else {
+                    0
+                },
+            );
+        }
+        Ok(response)
+    }
+
+    /// Plan an action, then sign and execute it.
+    ///
+    /// A transaction cancelled because a shared object is congested did not execute, so with
+    /// congestion retries enabled it is planned again, which picks up the new versions of its
+    /// owned objects, and resubmitted after a backoff. Congestion that outlasts the retries,
+    /// or any congestion when they are disabled, is a [`GameError::SharedObjectCongested`].
+    ///
+    /// Each attempt runs in a `transaction` tracing span, tagged with the digest once the
+    /// transaction is signed, so fetching, building, signing and executing it can be timed.
+    pub async fn execute(
+        &self,
+        action: &Action,
+    ) -> Result<SuiTransactionBlockResponse, anyhow::Error> {
+        let policy = self.rpc.congestion;
+        let mut attempt = 1;
+        loop {
+            let span = info_span!("transaction", attempt, digest = field::Empty);
+            let plan = self.plan(action).instrument(span.clone()).await?;
+            let outcome = self.execute_plan(plan).instrument(span).await;
+            let detail = match &outcome {
+                Ok(response) => match response.effects.as_ref().map(|e| e.status()) {
+                    Some(SuiExecutionStatus::Failure { error }) => error.clone(),
+                    _ => return outcome,
+                },
+                Err(e) => format!("{e:#}"),
+            };
+            if !retry::is_congestion_error(&detail) {
+                return outcome;
+            }
+            if !self.retry_congestion || attempt >= policy.attempts {
+                return Err(GameError::SharedObjectCongested {
+                    attempts: attempt,
+                    detail,
+                }
+                .into());
+            }
+            let backoff = policy.backoff(attempt);
+            self.record_decision("congestion_retry", || {
+                json!({
+                    "attempt": attempt,
+                    "backoff_ms": backoff.as_millis() as u64,
+                    "detail": detail,
+                })
+            });
+            warn!(
+                "Shared object congested, retrying in {:.1}s (attempt {} of {})",
+                backoff.as_secs_f64(),
+                attempt + 1,
+                policy.attempts
+            );
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    /// Total SUI balance of an address.
+    pub async fn balance(&self, owner: SuiAddress) -> Result<Balance, anyhow::Error> {
+        let result = self
+            .rpc
+            .read
+            .run(|| async { Ok(self.sui.coin_read_api().get_balance(owner, None).await?) })
+            .await;
+        self.record_rpc(
+            "suix_getBalance",
+            || json!({ "owner": owner }),
+            result.as_ref(),
+        );
+        result
+    }
+
+    /// Every object of the deployment's `gamecards` module owned by an address, with its
+    /// decoded fields only, see [`game::content_object_options`].
+    pub async fn cards(&self, owner: SuiAddress) -> Result<Vec<SuiObjectData>, anyhow::Error> {
+        let filter = SuiObjectDataFilter::MoveModule {
+            package: self.deployment.package,
+            module: Identifier::new("gamecards")?,
+        };
+        self.owned_objects(owner, Some(filter), game::content_object_options())
+            .await
+    }
+
+    /// Every object owned by an address that matches `filter`, fetched page by page.
+    pub async fn owned_objects(
+        &self,
+        owner: SuiAddress,
+        filter: Option<SuiObjectDataFilter>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectData>, anyhow::Error> {
+        let query = SuiObjectResponseQuery::new(filter, Some(options));
+
+        let mut objects = vec![];
+        let mut cursor = None;
+        loop {
+            let page = self
+                .rpc
+                .read
+                .run(|| async {
+                    Ok(self
+                        .sui
+                        .read_api()
+                        .get_owned_objects(owner, Some(query.clone()), cursor, None)
+                        .await?)
+                })
+                .await;
+            self.record_rpc(
+                "suix_getOwnedObjects",
+                || json!({ "owner": owner, "query": query, "cursor": cursor }),
+                page.as_ref(),
+            );
+            let page = page?;
+            objects.extend(page.data.into_iter().filter_map(|object| object.data));
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        Ok(objects)
+    }
+
+    /// An executed transaction with all of its details.
+    pub async fn transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<SuiTransactionBlockResponse, anyhow::Error> {
+        self.rpc.read.run(|| self.fetch_transaction(digest)).await
+    }
+
+    async fn fetch_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<SuiTransactionBlockResponse, anyhow::Error> {
+        let result = self
+            .sui
+            .read_api()
+            .get_transaction_with_options(
+                digest,
+                SuiTransactionBlockResponseOptions::full_content(),
+            )
+            .await
+            .map_err(anyhow::Error::from);
+        self.record_rpc(
+            "sui_getTransactionBlock",
+            || json!({ "digest": digest }),
+            result.as_ref(),
+        );
+        result
+    }
+
+    /// The kind of an executed transaction, with its programmable commands and inputs as
+    /// they were signed, decoded from the transaction's raw BCS bytes.
+    pub async fn transaction_kind(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<TransactionKind, anyhow::Error> {
+        let response = self
+            .rpc
+            .read
+            .run(|| async {
+                Ok(self
+                    .sui
+                    .read_api()
+                    .get_transaction_with_options(
+                        digest,
+                        SuiTransactionBlockResponseOptions::new().with_raw_input(),
+                    )
+                    .await?)
+            })
+            .await;
+        self.record_rpc(
+            "sui_getTransactionBlock",
+            || json!({ "digest": digest, "raw_input": true }),
+            response.as_ref(),
+        );
+        let response = response?;
+        let signed: SenderSignedData = bcs::from_bytes(&response.raw_transaction)?;
+        Ok(signed.transaction_data().kind().clone())
+    }
+
+    /// The most recent transactions sent by an address, newest first.
+    pub async fn history(
+        &self,
+        address: SuiAddress,
+        limit: usize,
+    ) -> Result<Vec<SuiTransactionBlockResponse>, anyhow::Error> {
+        self.rpc
+            .read
+            .run(|| self.history_once(address, limit))
+            .await
+    }
+
+    async fn history_once(
+        &self,
+        address: SuiAddress,
+        limit: usize,
+    ) -> Result<Vec<SuiTransactionBlockResponse>, anyhow::Error> {
+        let query = SuiTransactionBlockResponseQuery::new(
+            Some(TransactionFilter::FromAddress(address)),
+            Some(SuiTransactionBlockResponseOptions::new().with_effects()),
+        );
+
+        let mut transactions = vec![];
+        let mut cursor = None;
+        while transactions.len() < limit {
+            let page_size = limit - transactions.len();
+            let page = self
+                .sui
+                .read_api()
+                .query_transaction_blocks(query.clone(), cursor, Some(page_size), true)
+                .await
+                .map_err(anyhow::Error::from);
+            self.record_rpc(
+                "suix_queryTransactionBlocks",
+                || json!({ "query": query, "cursor": cursor, "limit": page_size }),
+                page.as_ref(),
+            );
+            let page = page?;
+            transactions.extend(page.data);
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        transactions.truncate(limit);
+        Ok(transactions)
+    }
+}
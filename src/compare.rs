@@ -0,0 +1,167 @@
+use anyhow::{anyhow, bail};
+use sui_json_rpc_types::Coin;
+use sui_sdk::types::{
+    base_types::ObjectID,
+    transaction::{
+        Argument, CallArg, Command, ObjectArg, ProgrammableTransaction, TransactionData,
+    },
+};
+
+use crate::{
+    client::{Action, GameClient},
+    gas::{self, GasEstimate},
+    plan::TransactionPlan,
+};
+
+/// Two ways of building `create_room` whose gas costs can be compared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Strategy {
+    /// Pass the game room as a mutable or as an immutable shared object. Only functions
+    /// that take the room by immutable reference succeed with the latter.
+    RoomMutability,
+    /// Pay with several coins merged into the gas coin by a `MergeCoins` command, or with
+    /// the same coins given as the gas payment, which the network merges itself.
+    GasCoins,
+}
+
+/// The dry run of one way of building the operation.
+pub struct Variant {
+    pub name: &'static str,
+    pub estimate: GasEstimate,
+}
+
+/// Dry-run `create_room` built both ways of `strategy`, in the order they are described.
+///
+/// `coins` is how many SUI coins of the sender pay for gas with [`Strategy::GasCoins`],
+/// including the gas coin itself. Nothing is signed or executed.
+pub async fn compare_create_room(
+    client: &GameClient,
+    card: ObjectID,
+    strategy: Strategy,
+    coins: usize,
+) -> Result<[Variant; 2], anyhow::Error> {
+    let plan = client
+        .plan_without_estimate(&Action::CreateRoom {
+            card,
+            extra_objects: vec![],
+            room_owner: None,
+        })
+        .await?;
+
+    let (first, second) = match strategy {
+        Strategy::RoomMutability => {
+            // The plan passes the room as the deployment says, so both sides are set explicitly
+            let room = client.deployment().room;
+            let mutable = with_room_mutability(plan.clone(), room, true)?;
+            let immutable = with_room_mutability(plan.clone(), room, false)?;
+            (
+                ("mutable room", mutable.transaction_data()),
+                ("immutable room", immutable.transaction_data()),
+            )
+        }
+        Strategy::GasCoins => {
+            let extra = extra_gas_coins(client, &plan, coins).await?;
+            let mut merged = plan.clone();
+            let sources = extra
+                .iter()
+                .map(|coin| {
+                    let input = u16::try_from(merged.inputs.len())?;
+                    merged
+                        .inputs
+                        .push(CallArg::Object(ObjectArg::ImmOrOwnedObject(
+                            coin.object_ref(),
+                        )));
+                    Ok(Argument::Input(input))
+                })
+                .collect::<Result<_, anyhow::Error>>()?;
+            merged
+                .commands
+                .push(Command::MergeCoins(Argument::GasCoin, sources));
+
+            let mut payment = vec![plan.gas_coin];
+            payment.extend(extra.iter().map(Coin::object_ref));
+            let pt = ProgrammableTransaction {
+                inputs: plan.inputs.clone(),
+                commands: plan.commands.clone(),
+            };
+            let paid_with_coins = TransactionData::new_programmable(
+                plan.sender,
+                payment,
+                pt,
+                plan.gas_budget,
+                plan.gas_price,
+            );
+            (
+                ("merged in the transaction", merged.transaction_data()),
+                ("several gas coins", paid_with_coins),
+            )
+        }
+    };
+
+    Ok([
+        Variant {
+            name: first.0,
+            estimate: dry_run(client, first.1).await?,
+        },
+        Variant {
+            name: second.0,
+            estimate: dry_run(client, second.1).await?,
+        },
+    ])
+}
+
+/// Dry-run one side of the comparison, which pays gas with the client's coin type like the
+/// plan it is built from.
+async fn dry_run(
+    client: &GameClient,
+    transaction_data: TransactionData,
+) -> Result<GasEstimate, anyhow::Error> {
+    gas::dry_run(client.sui(), transaction_data)
+        .await
+        .map_err(|e| match client.gas_coin_type() {
+            Some(coin_type) => e.context(format!("The network did not accept {coin_type} as gas")),
+            None => e,
+        })
+}
+
+/// The plan with the shared room input made mutable or immutable.
+fn with_room_mutability(
+    mut plan: TransactionPlan,
+    room: ObjectID,
+    mutable: bool,
+) -> Result<TransactionPlan, anyhow::Error> {
+    let input = plan
+        .inputs
+        .iter_mut()
+        .find_map(|input| match input {
+            CallArg::Object(ObjectArg::SharedObject {
+                id, mutable: flag, ..
+            }) if *id == room => Some(flag),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("The plan does not use the game room {room}"))?;
+    *input = mutable;
+    Ok(plan)
+}
+
+/// Other gas coins of the sender to pay for gas along with the plan's gas coin.
+async fn extra_gas_coins(
+    client: &GameClient,
+    plan: &TransactionPlan,
+    coins: usize,
+) -> Result<Vec<Coin>, anyhow::Error> {
+    if coins < 2 {
+        bail!("Comparing gas coin strategies needs at least 2 coins, got {coins}");
+    }
+    let mut extra = gas::coins_of_type(client.sui(), plan.sender, client.gas_coin_type()).await?;
+    extra.retain(|coin| coin.coin_object_id != plan.gas_coin.0);
+    if extra.len() + 1 < coins {
+        bail!(
+            "{} only has {} gas coins, {coins} are needed",
+            plan.sender,
+            extra.len() + 1
+        );
+    }
+    extra.truncate(coins - 1);
+    Ok(extra)
+}
@@ -0,0 +1,170 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use move_core_types::language_storage::StructTag;
+use serde::{de, Deserialize, Deserializer};
+use sui_sdk::types::{base_types::ObjectID, parse_sui_struct_tag};
+
+use crate::{game::Deployment, retry::RpcPolicies, utils::Network};
+
+/// Config file read from the working directory when no `--config` is given.
+pub const DEFAULT_CONFIG_FILE: &str = "game.toml";
+
+/// Settings read from a TOML config file. Command line flags take priority over them.
+///
+/// ```toml
+/// network = "testnet"
+/// package = "0x..."
+/// room = "0x..."
+/// card_type = "0x...::gamecards::Card"
+/// room_type = "0x...::gamecards::GameRoom"
+/// max_gas_budget = 1000000000
+/// default_sender = "alice"
+///
+/// [rpc.read]
+/// attempts = 5
+/// timeout_secs = 10
+/// backoff_ms = 200
+///
+/// [rpc.congestion]
+/// attempts = 6
+/// backoff_ms = 5000
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub network: Option<Network>,
+    pub package: Option<ObjectID>,
+    pub room: Option<ObjectID>,
+    #[serde(deserialize_with = "deserialize_struct_tag")]
+    pub card_type: Option<StructTag>,
+    #[serde(deserialize_with = "deserialize_struct_tag")]
+    pub room_type: Option<StructTag>,
+    /// Gas budgets above this many MIST need `--allow-high-budget`. Without it, only the
+    /// network's `max_tx_gas` limits the budget.
+    pub max_gas_budget: Option<u64>,
+    /// Address or keystore alias that signs transactions when `--sender` is not given,
+    /// instead of the wallet's active address.
+    pub default_sender: Option<String>,
+    pub rpc: RpcPolicies,
+}
+
+fn deserialize_struct_tag<'de, D>(deserializer: D) -> Result<Option<StructTag>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let tag = String::deserialize(deserializer)?;
+    parse_sui_struct_tag(&tag)
+        .map(Some)
+        .map_err(|e| de::Error::custom(format!("invalid struct tag `{tag}`: {e}")))
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        let contents =
+            fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("Invalid config {}", path.display()))
+    }
+
+    /// Load the given config file, or `game.toml` if it exists, or fall back to the defaults.
+    pub fn load_or_default(path: Option<&Path>) -> Result<Self, anyhow::Error> {
+        match path {
+            Some(path) => Self::load(path),
+            None => {
+                let path = PathBuf::from(DEFAULT_CONFIG_FILE);
+                if path.exists() {
+                    Self::load(&path)
+                } else {
+                    Ok(Self::default())
+                }
+            }
+        }
+    }
+
+    /// The configured deployment, with the built-in testnet deployment filling the gaps.
+    pub fn deployment(&self) -> Deployment {
+        let default = Deployment::default();
+        Deployment {
+            package: self.package.unwrap_or(default.package),
+            room: self.room.unwrap_or(default.room),
+            card_type: self.card_type.clone(),
+            room_type: self.room_type.clone(),
+            room_ref: None,
+        }
+    }
+
+    /// Check a config file without stopping at the first problem, returning every problem
+    /// found. Each top-level key, and each policy under `[rpc]`, is parsed on its own, so
+    /// one invalid value does not hide the others.
+    pub fn problems(contents: &str) -> Vec<String> {
+        Self::check(contents).problems
+    }
+
+    /// Like [`Config::problems`], but also keep the settings of the valid keys, so that
+    /// checks that depend on them can run despite the invalid ones.
+    pub fn check(contents: &str) -> ConfigCheck {
+        let table: toml::Table = match toml::from_str(contents) {
+            Ok(table) => table,
+            Err(e) => {
+                return ConfigCheck {
+                    config: None,
+                    invalid_keys: vec![],
+                    problems: vec![e.to_string()],
+                }
+            }
+        };
+
+        let mut valid = toml::Table::new();
+        let mut invalid_keys = vec![];
+        let mut problems = vec![];
+        for (key, value) in table {
+            match (key.as_str(), value) {
+                ("rpc", toml::Value::Table(policies)) => {
+                    let mut valid_policies = toml::Table::new();
+                    for (policy, value) in policies {
+                        let rpc = toml::Table::from_iter([(policy.clone(), value.clone())]);
+                        match RpcPolicies::deserialize(toml::Value::Table(rpc)) {
+                            Ok(_) => {
+                                valid_policies.insert(policy, value);
+                            }
+                            Err(e) => problems.push(format!("rpc.{policy}: {}", e.message())),
+                        }
+                    }
+                    valid.insert(key.clone(), toml::Value::Table(valid_policies));
+                }
+                (_, value) => {
+                    let single = toml::Table::from_iter([(key.clone(), value.clone())]);
+                    match Config::deserialize(toml::Value::Table(single)) {
+                        Ok(_) => {
+                            valid.insert(key.clone(), value);
+                        }
+                        Err(e) => {
+                            problems.push(format!("{key}: {}", e.message()));
+                            invalid_keys.push(key.clone());
+                        }
+                    }
+                }
+            }
+        }
+        // Every key was checked on its own, so together they cannot fail either
+        let config = Config::deserialize(toml::Value::Table(valid)).ok();
+        ConfigCheck {
+            config,
+            invalid_keys,
+            problems,
+        }
+    }
+}
+
+/// The outcome of checking a config file key by key, see [`Config::check`].
+#[derive(Clone, Debug)]
+pub struct ConfigCheck {
+    /// The settings of the valid keys, or `None` if the file is not valid TOML.
+    pub config: Option<Config>,
+    /// Top-level keys whose value is invalid. They are left unset in `config`.
+    pub invalid_keys: Vec<String>,
+    pub problems: Vec<String>,
+}
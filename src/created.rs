@@ -0,0 +1,47 @@
+use std::{fs, path::Path};
+
+use anyhow::Context;
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::{ObjectChange, SuiTransactionBlockResponse};
+use sui_sdk::types::base_types::ObjectID;
+
+/// How created object IDs are written to a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum IdsFormat {
+    /// One ID per line, which `--ids-file` and `--cards-file` read back
+    #[default]
+    Lines,
+    /// A JSON array of IDs
+    Json,
+}
+
+/// IDs of the objects created by a transaction, optionally only those of `object_type`.
+pub fn created_ids(
+    response: &SuiTransactionBlockResponse,
+    object_type: Option<&StructTag>,
+) -> Vec<ObjectID> {
+    response
+        .object_changes
+        .iter()
+        .flatten()
+        .filter_map(|change| match change {
+            ObjectChange::Created {
+                object_id,
+                object_type: created_type,
+                ..
+            } if object_type.is_none_or(|object_type| object_type == created_type) => {
+                Some(*object_id)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Write object IDs to a file, replacing its contents.
+pub fn write_ids(path: &Path, ids: &[ObjectID], format: IdsFormat) -> Result<(), anyhow::Error> {
+    let contents = match format {
+        IdsFormat::Lines => ids.iter().map(|id| format!("{id}\n")).collect(),
+        IdsFormat::Json => serde_json::to_string_pretty(ids)? + "\n",
+    };
+    fs::write(path, contents).with_context(|| format!("Cannot write {}", path.display()))
+}
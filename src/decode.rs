@@ -0,0 +1,236 @@
+use std::num::NonZeroUsize;
+
+use anyhow::{anyhow, bail};
+use futures::{future::BoxFuture, stream, StreamExt};
+use move_core_types::{
+    account_address::AccountAddress,
+    annotated_value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout, MoveValue},
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
+use serde_json::{json, Value};
+use sui_json_rpc_types::{
+    SuiMoveNormalizedType, SuiObjectDataOptions, SuiObjectResponse, SuiRawData,
+};
+use sui_sdk::{types::base_types::ObjectID, SuiClient};
+
+use crate::{game, modules::ModuleCache};
+
+/// Fetch objects and decode each of them as an `object_type`, decoding at most
+/// `concurrency` objects at a time.
+///
+/// The objects are fetched with as few multi-get requests as possible. The results are in
+/// the order of `ids`; an object that is missing, has another type or cannot be decoded
+/// gets an error without failing the others.
+pub async fn decode_objects(
+    sui: &SuiClient,
+    decoder: &MoveValueDecoder,
+    ids: &[ObjectID],
+    object_type: &StructTag,
+    concurrency: NonZeroUsize,
+) -> Result<Vec<Result<Value, anyhow::Error>>, anyhow::Error> {
+    let options = SuiObjectDataOptions::new().with_type().with_bcs();
+    let objects = game::multi_get_objects(sui, ids, options).await?;
+
+    Ok(stream::iter(ids.iter().zip(objects))
+        .map(|(id, object)| decode_object(decoder, *id, object, object_type))
+        .buffered(concurrency.get())
+        .collect()
+        .await)
+}
+
+async fn decode_object(
+    decoder: &MoveValueDecoder,
+    id: ObjectID,
+    object: SuiObjectResponse,
+    object_type: &StructTag,
+) -> Result<Value, anyhow::Error> {
+    let object = object
+        .into_object()
+        .map_err(|e| anyhow!("Cannot fetch object {id}: {e}"))?;
+    let raw = match object.bcs {
+        Some(SuiRawData::MoveObject(raw)) => raw,
+        Some(SuiRawData::Package(_)) => return Err(anyhow!("{id} is a package")),
+        None => return Err(anyhow!("The full node returned no BCS bytes for {id}")),
+    };
+    if raw.type_ != *object_type {
+        return Err(anyhow!("{id} has type {}, not {object_type}", raw.type_));
+    }
+    let type_tag = TypeTag::Struct(Box::new(raw.type_));
+    decoder.decode(&type_tag, &raw.bcs_bytes).await
+}
+
+/// Decodes BCS-encoded Move values, such as event payloads, dynamic fields and objects, into
+/// JSON using the Move struct layouts of their packages.
+///
+/// The normalized modules of each package are fetched once and then cached, see
+/// [`ModuleCache`], so decoding a stream of values only queries the full node for packages
+/// it has not seen yet.
+pub struct MoveValueDecoder {
+    modules: ModuleCache,
+}
+
+impl MoveValueDecoder {
+    pub fn new(sui: SuiClient) -> Self {
+        Self {
+            modules: ModuleCache::new(sui),
+        }
+    }
+
+    /// Decode the BCS bytes of a value of any Move type.
+    pub async fn decode(&self, type_tag: &TypeTag, bytes: &[u8]) -> Result<Value, anyhow::Error> {
+        let layout = self.type_layout(type_tag).await?;
+        let value = MoveValue::simple_deserialize(bytes, &layout)?;
+        Ok(move_value_to_json(&value))
+    }
+
+    fn struct_layout<'a>(
+        &'a self,
+        tag: &'a StructTag,
+    ) -> BoxFuture<'a, Result<MoveStructLayout, anyhow::Error>> {
+        Box::pin(async move {
+            let normalized = self
+                .modules
+                .struct_definition(
+                    ObjectID::from(tag.address),
+                    tag.module.as_str(),
+                    tag.name.as_str(),
+                )
+                .await?;
+            let field_types = normalized
+                .fields
+                .iter()
+                .map(|field| {
+                    Ok((
+                        field.name.clone(),
+                        type_tag(&field.type_, &tag.type_params)?,
+                    ))
+                })
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+            let mut fields = Vec::with_capacity(field_types.len());
+            for (name, field_type) in field_types {
+                let layout = self.type_layout(&field_type).await?;
+                fields.push(MoveFieldLayout::new(Identifier::new(name)?, layout));
+            }
+            Ok(MoveStructLayout {
+                type_: tag.clone(),
+                fields,
+            })
+        })
+    }
+
+    fn type_layout<'a>(
+        &'a self,
+        type_tag: &'a TypeTag,
+    ) -> BoxFuture<'a, Result<MoveTypeLayout, anyhow::Error>> {
+        Box::pin(async move {
+            Ok(match type_tag {
+                TypeTag::Bool => MoveTypeLayout::Bool,
+                TypeTag::U8 => MoveTypeLayout::U8,
+                TypeTag::U16 => MoveTypeLayout::U16,
+                TypeTag::U32 => MoveTypeLayout::U32,
+                TypeTag::U64 => MoveTypeLayout::U64,
+                TypeTag::U128 => MoveTypeLayout::U128,
+                TypeTag::U256 => MoveTypeLayout::U256,
+                TypeTag::Address => MoveTypeLayout::Address,
+                TypeTag::Signer => MoveTypeLayout::Signer,
+                TypeTag::Vector(element) => {
+                    MoveTypeLayout::Vector(Box::new(self.type_layout(element).await?))
+                }
+                TypeTag::Struct(tag) => MoveTypeLayout::Struct(self.struct_layout(tag).await?),
+            })
+        })
+    }
+}
+
+/// The concrete type of a struct field, with the type parameters of the struct substituted.
+pub fn type_tag(
+    field_type: &SuiMoveNormalizedType,
+    type_params: &[TypeTag],
+) -> Result<TypeTag, anyhow::Error> {
+    Ok(match field_type {
+        SuiMoveNormalizedType::Bool => TypeTag::Bool,
+        SuiMoveNormalizedType::U8 => TypeTag::U8,
+        SuiMoveNormalizedType::U16 => TypeTag::U16,
+        SuiMoveNormalizedType::U32 => TypeTag::U32,
+        SuiMoveNormalizedType::U64 => TypeTag::U64,
+        SuiMoveNormalizedType::U128 => TypeTag::U128,
+        SuiMoveNormalizedType::U256 => TypeTag::U256,
+        SuiMoveNormalizedType::Address => TypeTag::Address,
+        SuiMoveNormalizedType::Signer => TypeTag::Signer,
+        SuiMoveNormalizedType::Vector(element) => {
+            TypeTag::Vector(Box::new(type_tag(element, type_params)?))
+        }
+        SuiMoveNormalizedType::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => TypeTag::Struct(Box::new(StructTag {
+            address: AccountAddress::from_hex_literal(address)?,
+            module: Identifier::new(module.as_str())?,
+            name: Identifier::new(name.as_str())?,
+            type_params: type_arguments
+                .iter()
+                .map(|argument| type_tag(argument, type_params))
+                .collect::<Result<_, _>>()?,
+        })),
+        SuiMoveNormalizedType::TypeParameter(i) => type_params
+            .get(usize::from(*i))
+            .cloned()
+            .ok_or_else(|| anyhow!("Missing type argument {i}"))?,
+        SuiMoveNormalizedType::Reference(_) | SuiMoveNormalizedType::MutableReference(_) => {
+            bail!("Struct fields cannot be references")
+        }
+    })
+}
+
+/// Convert a decoded Move value to JSON.
+///
+/// Integers wider than 32 bits are rendered as strings, like the full node does, and
+/// strings, object IDs and UIDs are unwrapped into plain JSON strings.
+fn move_value_to_json(value: &MoveValue) -> Value {
+    match value {
+        MoveValue::Bool(b) => json!(b),
+        MoveValue::U8(n) => json!(n),
+        MoveValue::U16(n) => json!(n),
+        MoveValue::U32(n) => json!(n),
+        MoveValue::U64(n) => json!(n.to_string()),
+        MoveValue::U128(n) => json!(n.to_string()),
+        MoveValue::U256(n) => json!(n.to_string()),
+        MoveValue::Address(address) | MoveValue::Signer(address) => {
+            json!(address.to_hex_literal())
+        }
+        MoveValue::Vector(elements) => {
+            Value::Array(elements.iter().map(move_value_to_json).collect())
+        }
+        MoveValue::Struct(s) => {
+            let is_string = s.type_.address == AccountAddress::ONE
+                && matches!(s.type_.module.as_str(), "string" | "ascii")
+                && s.type_.name.as_str() == "String";
+            let is_id = s.type_.address == AccountAddress::TWO
+                && s.type_.module.as_str() == "object"
+                && matches!(s.type_.name.as_str(), "ID" | "UID");
+            match s.fields.as_slice() {
+                [(_, MoveValue::Vector(bytes))] if is_string => {
+                    let bytes: Vec<u8> = bytes
+                        .iter()
+                        .filter_map(|byte| match byte {
+                            MoveValue::U8(byte) => Some(*byte),
+                            _ => None,
+                        })
+                        .collect();
+                    json!(String::from_utf8_lossy(&bytes))
+                }
+                [(_, inner)] if is_id => move_value_to_json(inner),
+                fields => Value::Object(
+                    fields
+                        .iter()
+                        .map(|(name, value)| (name.to_string(), move_value_to_json(value)))
+                        .collect(),
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use anyhow::anyhow;
+use move_core_types::language_storage::TypeTag;
+use serde_json::{json, Value};
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiRawData};
+use sui_sdk::{
+    types::{
+        base_types::ObjectID,
+        dynamic_field::{DynamicFieldInfo, DynamicFieldType},
+    },
+    SuiClient,
+};
+use tracing::warn;
+
+use crate::{decode::MoveValueDecoder, game, transcript};
+
+/// A dynamic field of an object, with its name and value decoded to JSON.
+pub struct DecodedField {
+    pub name_type: TypeTag,
+    pub name: Value,
+    pub kind: DynamicFieldType,
+    /// The `Field` object holding the value, or for a dynamic object field the object
+    /// itself.
+    pub object_id: ObjectID,
+    pub value_type: String,
+    pub value: Value,
+}
+
+/// Every dynamic field of `parent`, requested `page_size` fields at a time.
+pub async fn dynamic_fields(
+    sui: &SuiClient,
+    parent: ObjectID,
+    page_size: usize,
+) -> Result<Vec<DynamicFieldInfo>, anyhow::Error> {
+    let mut fields = vec![];
+    let mut cursor = None;
+    loop {
+        let page = sui
+            .read_api()
+            .get_dynamic_fields(parent, cursor, Some(page_size))
+            .await
+            .map_err(anyhow::Error::from);
+        transcript::record_rpc(
+            "suix_getDynamicFields",
+            || json!({ "parent": parent, "cursor": cursor, "limit": page_size }),
+            page.as_ref(),
+        );
+        let page = page?;
+        fields.extend(page.data);
+        if !page.has_next_page {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    Ok(fields)
+}
+
+/// Fetch the object behind each dynamic field and decode the field's name and value.
+///
+/// A plain dynamic field is stored in a `0x2::dynamic_field::Field<Name, Value>` object,
+/// whose `value` is the field's value. A dynamic object field points to an object of its
+/// own, which is decoded whole. Values that cannot be decoded from their BCS bytes fall
+/// back to the JSON content parsed by the full node.
+pub async fn decode_fields(
+    sui: &SuiClient,
+    decoder: &MoveValueDecoder,
+    fields: &[DynamicFieldInfo],
+) -> Result<Vec<DecodedField>, anyhow::Error> {
+    let ids: Vec<_> = fields.iter().map(|field| field.object_id).collect();
+    let options = SuiObjectDataOptions::new()
+        .with_type()
+        .with_bcs()
+        .with_content();
+    let objects = game::multi_get_objects(sui, &ids, options).await?;
+
+    let mut decoded = Vec::with_capacity(fields.len());
+    for (field, object) in fields.iter().zip(objects) {
+        let object = object
+            .into_object()
+            .map_err(|e| anyhow!("Cannot fetch dynamic field {}: {e}", field.object_id))?;
+
+        let name = match decoder.decode(&field.name.type_, &field.bcs_name).await {
+            Ok(name) => name,
+            Err(e) => {
+                warn!("Cannot decode the name of {} ({e})", field.object_id);
+                field.name.value.clone()
+            }
+        };
+
+        let bcs = match &object.bcs {
+            Some(SuiRawData::MoveObject(raw)) => Some(raw),
+            _ => None,
+        };
+        let value = match bcs {
+            Some(raw) => {
+                let type_tag = TypeTag::Struct(Box::new(raw.type_.clone()));
+                decoder.decode(&type_tag, &raw.bcs_bytes).await
+            }
+            None => Err(anyhow!("The full node returned no BCS bytes")),
+        };
+        let value = match (value, &field.type_) {
+            (Ok(mut value), DynamicFieldType::DynamicField) => value["value"].take(),
+            (Ok(value), DynamicFieldType::DynamicObject) => value,
+            (Err(e), _) => {
+                warn!(
+                    "Cannot decode {} ({e}), using its parsed content",
+                    field.object_id
+                );
+                serde_json::to_value(&object.content)?
+            }
+        };
+
+        decoded.push(DecodedField {
+            name_type: field.name.type_.clone(),
+            name,
+            kind: field.type_.clone(),
+            object_id: field.object_id,
+            value_type: field.object_type.clone(),
+            value,
+        });
+    }
+    Ok(decoded)
+}
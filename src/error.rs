@@ -0,0 +1,43 @@
+use sui_sdk::types::base_types::{ObjectID, SequenceNumber};
+
+/// Failures that callers may want to tell apart from generic RPC or build errors.
+#[derive(Debug, thiserror::Error)]
+pub enum GameError {
+    #[error(
+        "Version {} of object {id} is not available on this full node, most likely because it \
+         has been pruned. Historical versions can be read from an archival full node or an \
+         indexer endpoint",
+        version.value()
+    )]
+    VersionPruned {
+        id: ObjectID,
+        version: SequenceNumber,
+    },
+
+    #[error(
+        "Object {id} has no version {} on this full node. Object versions are not contiguous, \
+         so this version may never have existed; if it did, it has likely been pruned and can \
+         be read from an archival full node or an indexer endpoint",
+        version.value()
+    )]
+    VersionNotFound {
+        id: ObjectID,
+        version: SequenceNumber,
+    },
+
+    #[error("The SuiNS name {name} does not resolve to an address")]
+    UnresolvedName { name: String },
+
+    #[error(
+        "A shared object of the transaction is congested, so it was cancelled without \
+         executing ({attempts} attempt(s)): {detail}"
+    )]
+    SharedObjectCongested { attempts: u32, detail: String },
+
+    #[error("Object {id} has type {found}, expected {expected}")]
+    WrongObjectType {
+        id: ObjectID,
+        expected: String,
+        found: String,
+    },
+}
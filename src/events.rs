@@ -0,0 +1,117 @@
+use std::future::Future;
+
+use anyhow::anyhow;
+use futures::StreamExt;
+use move_core_types::language_storage::TypeTag;
+use serde_json::Value;
+use sui_json_rpc_types::{EventFilter, SuiEvent};
+use sui_sdk::SuiClient;
+use tracing::warn;
+
+use crate::{decode::MoveValueDecoder, retry::RetryPolicy, utils};
+
+/// How the fields of an event are rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum EventFormat {
+    /// Decode the BCS payload against the event's Move struct layout, falling back to the
+    /// full node's parsed JSON if it cannot be decoded
+    #[default]
+    Decoded,
+    /// Print the JSON parsed by the full node as is
+    Parsed,
+}
+
+/// Render an event as its type, sender and transaction followed by its fields.
+pub fn describe_event(event: &SuiEvent, fields: &Value) -> String {
+    let fields = serde_json::to_string_pretty(fields).unwrap_or_else(|_| fields.to_string());
+    format!(
+        "{} from {} in {}\n{fields}",
+        event.type_, event.sender, event.id.tx_digest
+    )
+}
+
+/// Decodes event payloads into JSON using the Move struct layouts of their packages, see
+/// [`MoveValueDecoder`].
+pub struct EventDecoder {
+    format: EventFormat,
+    values: MoveValueDecoder,
+}
+
+impl EventDecoder {
+    pub fn new(sui: SuiClient, format: EventFormat) -> Self {
+        Self {
+            format,
+            values: MoveValueDecoder::new(sui),
+        }
+    }
+
+    /// The fields of an event as JSON, in the decoder's format.
+    pub async fn fields(&self, event: &SuiEvent) -> Value {
+        if self.format == EventFormat::Parsed {
+            return event.parsed_json.clone();
+        }
+        match self.decode(event).await {
+            Ok(fields) => fields,
+            Err(e) => {
+                warn!("Cannot decode {} ({e}), using the parsed JSON", event.type_);
+                event.parsed_json.clone()
+            }
+        }
+    }
+
+    /// Decode the BCS payload of an event against its Move struct layout.
+    pub async fn decode(&self, event: &SuiEvent) -> Result<Value, anyhow::Error> {
+        let type_tag = TypeTag::Struct(Box::new(event.type_.clone()));
+        self.values.decode(&type_tag, &event.bcs).await
+    }
+}
+
+/// Subscribe to the events matching `filter` on the full node at `rpc_url` and call
+/// `on_event` for each one as it arrives.
+///
+/// When the subscription drops, it is re-established after the policy's backoff. The
+/// function only returns once `policy.attempts` consecutive connections failed without
+/// delivering a single event.
+pub async fn watch_events<F, Fut>(
+    rpc_url: &str,
+    filter: EventFilter,
+    policy: &RetryPolicy,
+    mut on_event: F,
+) -> Result<(), anyhow::Error>
+where
+    F: FnMut(SuiEvent) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut failures = 0;
+    loop {
+        let error = match utils::ws_client_at(rpc_url).await {
+            Ok(sui) => match sui.event_api().subscribe_event(filter.clone()).await {
+                Ok(mut events) => {
+                    let mut error = anyhow!("The full node closed the subscription");
+                    while let Some(event) = events.next().await {
+                        match event {
+                            Ok(event) => {
+                                failures = 0;
+                                on_event(event).await;
+                            }
+                            Err(e) => {
+                                error = e.into();
+                                break;
+                            }
+                        }
+                    }
+                    error
+                }
+                Err(e) => e.into(),
+            },
+            Err(e) => e,
+        };
+
+        failures += 1;
+        if failures >= policy.attempts {
+            return Err(error.context("Giving up on the event subscription"));
+        }
+        warn!("Event subscription lost ({error}), reconnecting");
+        tokio::time::sleep(policy.backoff(failures)).await;
+    }
+}
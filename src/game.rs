@@ -0,0 +1,1083 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    future::Future,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail};
+use futures::future::BoxFuture;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde_json::json;
+use shared_crypto::intent::Intent;
+use sui_config::{sui_config_dir, SUI_KEYSTORE_FILENAME};
+use sui_json_rpc_types::{
+    SuiMoveAbility, SuiMoveNormalizedType, SuiObjectData, SuiObjectDataOptions, SuiObjectResponse,
+    SuiPastObjectResponse, SuiTransactionBlockResponse,
+};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
+use sui_sdk::{
+    rpc_types::SuiTransactionBlockResponseOptions,
+    types::{
+        base_types::{ObjectID, ObjectRef, ObjectType, SequenceNumber, SuiAddress},
+        crypto::SignatureScheme,
+        digests::TransactionDigest,
+        error::SuiObjectResponseError,
+        object::Owner,
+        quorum_driver_types::ExecuteTransactionRequestType,
+        transaction::{
+            Argument, Command, ObjectArg, ProgrammableMoveCall, ProgrammableTransaction,
+            Transaction, TransactionData,
+        },
+        Identifier,
+    },
+    SuiClient,
+};
+use tracing::{info, instrument, warn};
+
+use crate::{
+    decode,
+    error::GameError,
+    gas,
+    modules::ModuleCache,
+    ptb::{object_id, PtbBuilder},
+    transcript,
+};
+
+/// Package that publishes the `gamecards` module on testnet.
+pub const GAME_PACKAGE_ID: &str =
+    "0xc74620c25579b75ac8f6d0d670a4663944ff7f29d6e856f6b33e0a35a34c5a06";
+/// Shared game room object passed as the first argument of `create_room`.
+pub const GAME_ROOM_ID: &str = "0x52509952e7b80b08880238e9737e8f70e223418816e5a85bf82575ef84ecc545";
+/// Game card used by `create_room` when no card is given.
+pub const GAME_CARD_ID: &str = "0x440b328ba3c90f203f439f6fc4c5aa40b7ca41d28317d5bb9b6c0207cfebc693";
+
+/// The on-chain objects of a `gamecards` deployment.
+#[derive(Clone, Debug)]
+pub struct Deployment {
+    pub package: ObjectID,
+    pub room: ObjectID,
+    /// Exact Move type of game cards. When unset, any type declared in the package's
+    /// `gamecards` module is accepted as a card.
+    pub card_type: Option<StructTag>,
+    /// Exact Move type of the rooms `create_room` returns as owned objects. When unset, it is
+    /// the type of the room `create_room` takes as its first parameter.
+    pub room_type: Option<StructTag>,
+    /// How the room is passed to `create_room`. When unset, the room is fetched to find the
+    /// version it was shared at and is passed mutably.
+    pub room_ref: Option<RoomRef>,
+}
+
+impl Default for Deployment {
+    fn default() -> Self {
+        Self {
+            package: ObjectID::from_hex_literal(GAME_PACKAGE_ID).unwrap(),
+            room: ObjectID::from_hex_literal(GAME_ROOM_ID).unwrap(),
+            card_type: None,
+            room_type: None,
+            room_ref: None,
+        }
+    }
+}
+
+/// The shared-object input of the game room, for callers that track it themselves so that
+/// the room is never fetched.
+///
+/// Nothing checks it against the network: a version the room was not shared at fails the
+/// transaction when it is submitted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoomRef {
+    /// The version at which the room became shared, which never changes afterwards.
+    pub initial_shared_version: SequenceNumber,
+    pub mutable: bool,
+}
+
+impl Deployment {
+    /// The type an object must have to be passed to `create_room` as a card.
+    pub fn expected_card_type(&self) -> ExpectedType {
+        match &self.card_type {
+            Some(card_type) => ExpectedType::Exact(card_type.clone()),
+            None => ExpectedType::Module {
+                package: self.package,
+                module: "gamecards".to_string(),
+            },
+        }
+    }
+}
+
+/// The Move type an object passed to a call is expected to have.
+#[derive(Clone, Debug)]
+pub enum ExpectedType {
+    /// Exactly this struct type.
+    Exact(StructTag),
+    /// Any struct declared in this module.
+    Module { package: ObjectID, module: String },
+}
+
+impl ExpectedType {
+    pub fn matches(&self, found: &StructTag) -> bool {
+        match self {
+            ExpectedType::Exact(expected) => expected == found,
+            ExpectedType::Module { package, module } => {
+                ObjectID::from(found.address) == *package && found.module.as_str() == module
+            }
+        }
+    }
+}
+
+impl fmt::Display for ExpectedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpectedType::Exact(expected) => write!(f, "{expected}"),
+            ExpectedType::Module { package, module } => write!(f, "{package}::{module}::*"),
+        }
+    }
+}
+
+/// Fail with [`GameError::WrongObjectType`] unless the object has the expected type.
+pub fn check_object_type(
+    object: &SuiObjectData,
+    expected: &ExpectedType,
+) -> Result<(), anyhow::Error> {
+    let found = match &object.type_ {
+        Some(ObjectType::Struct(move_type)) => StructTag::from(move_type.clone()),
+        other => {
+            return Err(GameError::WrongObjectType {
+                id: object.object_id,
+                expected: expected.to_string(),
+                found: other
+                    .as_ref()
+                    .map(|t| t.to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+            }
+            .into())
+        }
+    };
+    if !expected.matches(&found) {
+        return Err(GameError::WrongObjectType {
+            id: object.object_id,
+            expected: expected.to_string(),
+            found: found.to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// The game card used when none is given on the command line.
+pub fn default_card() -> ObjectID {
+    ObjectID::from_hex_literal(GAME_CARD_ID).unwrap()
+}
+
+/// Options that ask the full node for every part of an object.
+pub fn full_object_options() -> SuiObjectDataOptions {
+    SuiObjectDataOptions {
+        show_type: true,
+        show_owner: true,
+        show_previous_transaction: true,
+        show_display: true,
+        show_content: true,
+        show_bcs: true,
+        show_storage_rebate: true,
+    }
+}
+
+/// Options that only ask the full node for the decoded Move fields of an object.
+///
+/// The ID, version and digest are always returned, and the content carries the object's
+/// type. Leaving out the BCS bytes (the same fields encoded a second time), the display
+/// metadata, the owner and the previous transaction keeps the response of a field-reading
+/// query to about the size of the fields' JSON. For a game card with an ID and one `u64`
+/// field, the compact JSON of the object is 427 bytes with these options and 986 bytes with
+/// [`full_object_options`], 57% less; objects with more fields save more, since their BCS
+/// grows with them.
+pub fn content_object_options() -> SuiObjectDataOptions {
+    SuiObjectDataOptions::new().with_content()
+}
+
+/// Fetch an object with all of its details, failing if it does not exist.
+pub async fn get_object(sui: &SuiClient, id: ObjectID) -> Result<SuiObjectData, anyhow::Error> {
+    get_object_with_options(sui, id, full_object_options()).await
+}
+
+/// Fetch the parts of an object selected by `options`, failing if it does not exist.
+#[instrument(name = "fetch_object", skip(sui, options), fields(id = %id))]
+pub async fn get_object_with_options(
+    sui: &SuiClient,
+    id: ObjectID,
+    options: SuiObjectDataOptions,
+) -> Result<SuiObjectData, anyhow::Error> {
+    let result = match sui
+        .read_api()
+        .get_object_with_options(id, options.clone())
+        .await
+    {
+        Ok(response) => response
+            .into_object()
+            .map_err(|e| anyhow!("Cannot fetch object {id}: {e}")),
+        Err(e) => Err(e.into()),
+    };
+    transcript::record_rpc(
+        "sui_getObject",
+        || json!({ "id": id, "options": options }),
+        result.as_ref(),
+    );
+    result
+}
+
+/// Whether an object can be found on the network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ObjectStatus {
+    Exists { version: u64 },
+    Deleted { version: u64 },
+    NotFound,
+}
+
+impl fmt::Display for ObjectStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectStatus::Exists { version } => write!(f, "exists (version {version})"),
+            ObjectStatus::Deleted { version } => write!(f, "deleted (version {version})"),
+            ObjectStatus::NotFound => f.write_str("not found"),
+        }
+    }
+}
+
+/// Most objects the full node returns for a single multi-get request.
+const MULTI_GET_LIMIT: usize = 50;
+
+/// Fetch many objects with as few requests as possible, in the order of `ids`.
+pub async fn multi_get_objects(
+    sui: &SuiClient,
+    ids: &[ObjectID],
+    options: SuiObjectDataOptions,
+) -> Result<Vec<SuiObjectResponse>, anyhow::Error> {
+    let mut responses = Vec::with_capacity(ids.len());
+    for chunk in ids.chunks(MULTI_GET_LIMIT) {
+        let result = sui
+            .read_api()
+            .multi_get_object_with_options(chunk.to_vec(), options.clone())
+            .await
+            .map_err(anyhow::Error::from);
+        transcript::record_rpc(
+            "sui_multiGetObjects",
+            || json!({ "ids": chunk, "options": options }),
+            result.as_ref(),
+        );
+        responses.extend(result?);
+    }
+    Ok(responses)
+}
+
+/// Whether each object of `ids` exists, was deleted, or was never found.
+pub async fn object_statuses(
+    sui: &SuiClient,
+    ids: &[ObjectID],
+) -> Result<Vec<ObjectStatus>, anyhow::Error> {
+    let responses = multi_get_objects(sui, ids, SuiObjectDataOptions::new()).await?;
+    Ok(responses
+        .into_iter()
+        .map(|response| match (response.data, response.error) {
+            (Some(data), _) => ObjectStatus::Exists {
+                version: data.version.value(),
+            },
+            (None, Some(SuiObjectResponseError::Deleted { version, .. })) => {
+                ObjectStatus::Deleted {
+                    version: version.value(),
+                }
+            }
+            _ => ObjectStatus::NotFound,
+        })
+        .collect())
+}
+
+/// The identifier of the chain the full node follows.
+pub async fn chain_identifier(sui: &SuiClient) -> Result<String, anyhow::Error> {
+    let result = sui
+        .read_api()
+        .get_chain_identifier()
+        .await
+        .map_err(anyhow::Error::from);
+    transcript::record_rpc("sui_getChainIdentifier", || json!({}), result.as_ref());
+    result
+}
+
+/// The sequence number of the latest checkpoint the full node has executed.
+pub async fn latest_checkpoint(sui: &SuiClient) -> Result<u64, anyhow::Error> {
+    let result = sui
+        .read_api()
+        .get_latest_checkpoint_sequence_number()
+        .await
+        .map_err(anyhow::Error::from);
+    transcript::record_rpc(
+        "sui_getLatestCheckpointSequenceNumber",
+        || json!({}),
+        result.as_ref(),
+    );
+    result
+}
+
+/// Fetch an object as it was at a past version.
+///
+/// Full nodes prune old object versions; a full node that says so is reported as
+/// [`GameError::VersionPruned`]. Object versions are sparse, so a version the full node
+/// simply does not have is [`GameError::VersionNotFound`], since it may never have existed.
+pub async fn get_past_object(
+    sui: &SuiClient,
+    id: ObjectID,
+    version: SequenceNumber,
+    options: SuiObjectDataOptions,
+) -> Result<SuiObjectData, anyhow::Error> {
+    let result = sui
+        .read_api()
+        .try_get_parsed_past_object(id, version, options.clone())
+        .await
+        .map_err(anyhow::Error::from);
+    transcript::record_rpc(
+        "sui_tryGetPastObject",
+        || json!({ "id": id, "version": version, "options": options }),
+        result.as_ref(),
+    );
+    let response = match result {
+        Ok(response) => response,
+        Err(e) if is_pruned_error(&e.to_string()) => {
+            return Err(GameError::VersionPruned { id, version }.into())
+        }
+        Err(e) => return Err(e),
+    };
+
+    match response {
+        SuiPastObjectResponse::VersionFound(data) => Ok(data),
+        SuiPastObjectResponse::VersionNotFound(id, version) => {
+            Err(GameError::VersionNotFound { id, version }.into())
+        }
+        SuiPastObjectResponse::ObjectNotExists(id) => bail!("Object {id} does not exist"),
+        SuiPastObjectResponse::ObjectDeleted(object_ref) => bail!(
+            "Object {} was deleted at version {}",
+            object_ref.object_id,
+            object_ref.version.value()
+        ),
+        SuiPastObjectResponse::VersionTooHigh {
+            object_id,
+            asked_version,
+            latest_version,
+        } => bail!(
+            "Object {object_id} has no version {} yet, its latest version is {}",
+            asked_version.value(),
+            latest_version.value()
+        ),
+    }
+}
+
+/// Whether an RPC error message says the requested object version has been pruned.
+fn is_pruned_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("pruned") || (message.contains("version") && message.contains("not available"))
+}
+
+/// What a `create_room` transaction is made of, besides the deployment and the sender.
+#[derive(Clone, Copy, Debug)]
+pub struct CreateRoom<'a> {
+    pub card: ObjectID,
+    /// Objects owned by the sender, passed after the cards in order.
+    pub extra_objects: &'a [ObjectID],
+    pub room_owner: Option<SuiAddress>,
+    /// Whether the card's owner and type are checked before it is used.
+    pub check_type: bool,
+}
+
+/// Build the programmable transaction that calls `gamecards::create_room`
+/// with the shared game room and a vector holding a single game card.
+///
+/// Variants of `create_room` that take more owned objects get `extra_objects` as further
+/// arguments, in order. Each of them must exist and be owned by the sender.
+///
+/// Unless `check_type` is false, the card must be owned by `sender` and have the
+/// deployment's card type, so that passing some other object fails here rather than with a
+/// Move abort or a signature error.
+///
+/// Objects returned by `create_room` are transferred in the same transaction: an owned room,
+/// that is an object of the deployment's room type (see [`room_type`]), goes to `room_owner`,
+/// or to the sender if it is `None`, and any other object, such as a capability, stays with
+/// the sender. When `create_room` returns no room, `room_owner` is ignored with a warning.
+pub async fn create_room_ptb(
+    sui: &SuiClient,
+    modules: &ModuleCache,
+    deployment: &Deployment,
+    sender: SuiAddress,
+    request: CreateRoom<'_>,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let ptb = create_room_builder(sui, modules, deployment, sender, request).await?;
+    Ok(ptb.finish())
+}
+
+/// The version at which the game room became shared, failing if it is not shared.
+async fn room_initial_version(
+    sui: &SuiClient,
+    room: ObjectID,
+) -> Result<SequenceNumber, anyhow::Error> {
+    match get_object(sui, room).await?.owner {
+        Some(Owner::Shared {
+            initial_shared_version,
+        }) => Ok(initial_shared_version),
+        owner => bail!("Game room {room} is not a shared object (owner: {owner:?})"),
+    }
+}
+
+/// Like [`create_room_ptb`], but return the builder so its recorded steps can be reviewed.
+#[instrument(name = "build_create_room", skip_all, fields(card = %request.card))]
+pub async fn create_room_builder(
+    sui: &SuiClient,
+    modules: &ModuleCache,
+    deployment: &Deployment,
+    sender: SuiAddress,
+    request: CreateRoom<'_>,
+) -> Result<PtbBuilder, anyhow::Error> {
+    let CreateRoom {
+        card,
+        extra_objects,
+        room_owner,
+        check_type,
+    } = request;
+    // The game room is a shared object, so it is referenced by the version it was shared at
+    let room = match deployment.room_ref {
+        Some(room_ref) => ObjectArg::SharedObject {
+            id: deployment.room,
+            initial_shared_version: room_ref.initial_shared_version,
+            mutable: room_ref.mutable,
+        },
+        None => ObjectArg::SharedObject {
+            id: deployment.room,
+            initial_shared_version: room_initial_version(sui, deployment.room).await?,
+            mutable: true,
+        },
+    };
+
+    // The game card and the extra objects are owned by the sender and are referenced by
+    // their latest version. Without type checks the card is passed as it is, so that the
+    // network decides whether the sender may use it.
+    let card = if check_type {
+        let card_type = deployment.expected_card_type();
+        owned_object_arg(sui, card, sender, Some(&card_type)).await?
+    } else {
+        ObjectArg::ImmOrOwnedObject(get_object(sui, card).await?.object_ref())
+    };
+    let mut extra_arguments = vec![];
+    for &object in extra_objects {
+        extra_arguments.push(owned_object_arg(sui, object, sender, None).await?);
+    }
+
+    let returned =
+        returned_objects(modules, deployment.package, "gamecards", "create_room").await?;
+    let room_type = room_type(modules, deployment).await?;
+    let returns_room = returned
+        .iter()
+        .any(|(_, object_type)| is_room(object_type, &room_type));
+    if let (false, Some(room_owner)) = (returns_room, room_owner) {
+        warn!("create_room returns no {room_type}, ignoring the room owner {room_owner}");
+    }
+    let returned = returned_recipients(&returned, &room_type, sender, room_owner);
+    build_create_room(deployment, room, card, &extra_arguments, &returned)
+}
+
+/// The type of the rooms `create_room` creates: the deployment's `room_type` if it is set,
+/// and otherwise the type of the room `create_room` takes, read from its normalized signature.
+pub async fn room_type(
+    modules: &ModuleCache,
+    deployment: &Deployment,
+) -> Result<StructTag, anyhow::Error> {
+    if let Some(room_type) = &deployment.room_type {
+        return Ok(room_type.clone());
+    }
+    let signature = modules
+        .function(deployment.package, "gamecards", "create_room")
+        .await?;
+    let parameter = match signature.parameters.first() {
+        Some(
+            SuiMoveNormalizedType::Reference(room) | SuiMoveNormalizedType::MutableReference(room),
+        ) => room.as_ref(),
+        Some(room) => room,
+        None => bail!("create_room takes no room, set the room type explicitly"),
+    };
+    match decode::type_tag(parameter, &[]) {
+        Ok(TypeTag::Struct(room_type)) => Ok(*room_type),
+        _ => bail!(
+            "The room parameter of create_room is not a concrete struct, set the room type explicitly"
+        ),
+    }
+}
+
+/// The address each object returned by `create_room` is transferred to, by its position:
+/// objects of `room_type` go to `room_owner`, or to `sender` if it is `None`, and any other
+/// object stays with `sender`.
+pub fn returned_recipients(
+    returned: &[(u16, TypeTag)],
+    room_type: &StructTag,
+    sender: SuiAddress,
+    room_owner: Option<SuiAddress>,
+) -> Vec<(u16, SuiAddress)> {
+    returned
+        .iter()
+        .map(|(i, object_type)| {
+            let recipient = match is_room(object_type, room_type) {
+                true => room_owner.unwrap_or(sender),
+                false => sender,
+            };
+            (*i, recipient)
+        })
+        .collect()
+}
+
+fn is_room(object_type: &TypeTag, room_type: &StructTag) -> bool {
+    matches!(object_type, TypeTag::Struct(tag) if **tag == *room_type)
+}
+
+/// Build the `create_room` transaction from inputs that are already resolved.
+///
+/// `returned` holds the positions of the objects returned by `create_room`, each with the
+/// address it is transferred to. Nothing is fetched from a full node, so this also works
+/// offline with placeholder object references.
+pub fn build_create_room(
+    deployment: &Deployment,
+    room: ObjectArg,
+    card: ObjectArg,
+    extra_objects: &[ObjectArg],
+    returned: &[(u16, SuiAddress)],
+) -> Result<PtbBuilder, anyhow::Error> {
+    let mut ptb = PtbBuilder::new();
+
+    // 1) The shared game room comes first
+    let access = match room {
+        ObjectArg::SharedObject { mutable: false, .. } => "an immutable",
+        _ => "a mutable",
+    };
+    ptb.step(format!(
+        "Add the shared game room {} as {access} input",
+        deployment.room
+    ));
+    let room = ptb.object(room)?;
+
+    // 2) Then the game card owned by the sender
+    ptb.step(format!(
+        "Add the game card {} as an owned input",
+        object_id(&card)
+    ));
+    let card = ptb.object(card)?;
+
+    // 3) Extra objects are owned by the sender too and follow the cards in order
+    let mut extra_arguments = vec![];
+    for &object in extra_objects {
+        ptb.step(format!(
+            "Add the extra object {} as an owned input",
+            object_id(&object)
+        ));
+        extra_arguments.push(ptb.object(object)?);
+    }
+
+    // 4) Wrap the card in a vector and pass it to `create_room` along with the room
+    ptb.step("Wrap the card in a vector");
+    let cards = ptb.command(Command::MakeMoveVec(None, vec![card]));
+    let mut arguments = vec![room, cards];
+    arguments.extend(extra_arguments);
+    ptb.step(format!(
+        "Call {}::gamecards::create_room with the room, the cards and the extra objects",
+        deployment.package
+    ));
+    let created = ptb.command(Command::MoveCall(Box::new(ProgrammableMoveCall {
+        package: deployment.package,
+        module: Identifier::new("gamecards")?,
+        function: Identifier::new("create_room")?,
+        type_arguments: vec![],
+        arguments,
+    })));
+
+    // 5) Objects returned by `create_room` cannot be dropped, so they go to their recipients
+    let Argument::Result(call) = created else {
+        unreachable!("commands always produce a result argument")
+    };
+    let mut by_recipient: BTreeMap<SuiAddress, Vec<Argument>> = BTreeMap::new();
+    for &(i, recipient) in returned {
+        by_recipient
+            .entry(recipient)
+            .or_default()
+            .push(Argument::NestedResult(call, i));
+    }
+    for (recipient, values) in by_recipient {
+        ptb.step(format!(
+            "Transfer {} returned objects to {recipient}",
+            values.len()
+        ));
+        ptb.transfer_args(recipient, values);
+    }
+
+    Ok(ptb)
+}
+
+/// Positions and types of the objects among the values returned by a Move function.
+///
+/// Returned objects have to be transferred before the transaction ends, which
+/// `TransferObjects` can only do for structs with `key` and `store`. Values with `drop`,
+/// including vectors and structs whose contents can all be dropped, are simply discarded.
+/// Anything else, such as an object without `store` or a vector of objects, fails here,
+/// since the transaction could not consume it.
+async fn returned_objects(
+    modules: &ModuleCache,
+    package: ObjectID,
+    module: &str,
+    function: &str,
+) -> Result<Vec<(u16, TypeTag)>, anyhow::Error> {
+    let signature = modules.function(package, module, function).await?;
+
+    let mut objects = vec![];
+    for (i, return_type) in signature.return_.iter().enumerate() {
+        if let SuiMoveNormalizedType::Reference(_) | SuiMoveNormalizedType::MutableReference(_) =
+            return_type
+        {
+            bail!("{function} returns a reference, which a transaction cannot use");
+        }
+        let return_type = decode::type_tag(return_type, &[])
+            .map_err(|e| e.context(format!("{function} has a generic return value")))?;
+        let abilities = abilities(modules, &return_type).await?;
+        if abilities.contains(&SuiMoveAbility::Key) {
+            if !abilities.contains(&SuiMoveAbility::Store) {
+                bail!("{function} returns a {return_type}, which has no `store` ability and cannot be transferred by this transaction");
+            }
+            objects.push((u16::try_from(i)?, return_type));
+        } else if !abilities.contains(&SuiMoveAbility::Drop) {
+            bail!(
+                "{function} returns a {return_type}, which can be neither transferred nor dropped"
+            );
+        }
+    }
+    Ok(objects)
+}
+
+/// The abilities of a concrete Move type. A vector, or an instance of a generic struct, has
+/// an ability only if its element, or each of its non-phantom type arguments, has it too.
+fn abilities<'a>(
+    modules: &'a ModuleCache,
+    move_type: &'a TypeTag,
+) -> BoxFuture<'a, Result<Vec<SuiMoveAbility>, anyhow::Error>> {
+    Box::pin(async move {
+        Ok(match move_type {
+            TypeTag::Struct(tag) => {
+                let definition = modules
+                    .struct_definition(
+                        ObjectID::from(tag.address),
+                        tag.module.as_str(),
+                        tag.name.as_str(),
+                    )
+                    .await?;
+                let mut abilities = definition.abilities.abilities;
+                for (parameter, argument) in definition.type_parameters.iter().zip(&tag.type_params)
+                {
+                    if parameter.is_phantom {
+                        continue;
+                    }
+                    let argument = self::abilities(modules, argument).await?;
+                    // `key` needs its arguments to have `store`, the other abilities themselves
+                    abilities.retain(|ability| match ability {
+                        SuiMoveAbility::Key => argument.contains(&SuiMoveAbility::Store),
+                        other => argument.contains(other),
+                    });
+                }
+                abilities
+            }
+            TypeTag::Vector(element) => {
+                let mut abilities = abilities(modules, element).await?;
+                abilities.retain(|ability| *ability != SuiMoveAbility::Key);
+                abilities
+            }
+            TypeTag::Signer => vec![SuiMoveAbility::Drop],
+            // The other primitive values have `copy`, `drop` and `store`
+            _ => vec![
+                SuiMoveAbility::Copy,
+                SuiMoveAbility::Drop,
+                SuiMoveAbility::Store,
+            ],
+        })
+    })
+}
+
+/// A game card and the address it should be transferred to, written as `<card>:<recipient>`.
+#[derive(Clone, Copy, Debug)]
+pub struct CardAssignment {
+    pub card: ObjectID,
+    pub recipient: SuiAddress,
+}
+
+impl FromStr for CardAssignment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (card, recipient) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Expected `<card>:<recipient>`, got `{s}`"))?;
+        Ok(Self {
+            card: ObjectID::from_hex_literal(card.trim())?,
+            recipient: SuiAddress::from_str(recipient.trim())?,
+        })
+    }
+}
+
+/// Fetch an object that must be owned by `owner`, and have `expected_type` if given, and
+/// return it as a transaction input.
+pub async fn owned_object_arg(
+    sui: &SuiClient,
+    id: ObjectID,
+    owner: SuiAddress,
+    expected_type: Option<&ExpectedType>,
+) -> Result<ObjectArg, anyhow::Error> {
+    let object = get_object(sui, id).await?;
+    match object.owner {
+        Some(Owner::AddressOwner(address)) if address == owner => {}
+        other => bail!("Object {id} is not owned by {owner} (owner: {other:?})"),
+    }
+    if let Some(expected_type) = expected_type {
+        check_object_type(&object, expected_type)?;
+    }
+    Ok(ObjectArg::ImmOrOwnedObject(object.object_ref()))
+}
+
+/// Build a programmable transaction that transfers every card to its assigned recipient.
+/// Cards going to the same recipient share a single `TransferObjects` command.
+#[instrument(name = "build_distribute_cards", skip_all)]
+pub async fn distribute_cards_ptb(
+    sui: &SuiClient,
+    sender: SuiAddress,
+    assignments: &[CardAssignment],
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut ptb = PtbBuilder::new();
+    let mut by_recipient: BTreeMap<SuiAddress, Vec<Argument>> = BTreeMap::new();
+    let mut seen = BTreeSet::new();
+
+    for assignment in assignments {
+        if !seen.insert(assignment.card) {
+            bail!("Card {} is assigned more than once", assignment.card);
+        }
+        let card = owned_object_arg(sui, assignment.card, sender, None).await?;
+        let argument = ptb.object(card)?;
+        by_recipient
+            .entry(assignment.recipient)
+            .or_default()
+            .push(argument);
+    }
+
+    for (recipient, cards) in by_recipient {
+        ptb.transfer_args(recipient, cards);
+    }
+    Ok(ptb.finish())
+}
+
+/// Build a programmable transaction that calls `gamecards::<function>` with a shared room.
+///
+/// The function must take the room as its only parameter, apart from the `TxContext`
+/// that the network passes itself; a function that needs more cannot be called this way.
+pub async fn join_room_ptb(
+    sui: &SuiClient,
+    modules: &ModuleCache,
+    deployment: &Deployment,
+    room: ObjectID,
+    function: &str,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let signature = modules
+        .function(deployment.package, "gamecards", function)
+        .await?;
+    let parameters = signature
+        .parameters
+        .iter()
+        .filter(|parameter| !is_tx_context(parameter))
+        .count();
+    if parameters != 1 {
+        bail!("gamecards::{function} takes {parameters} parameters, but only the room can be passed to it");
+    }
+
+    let mut ptb = PtbBuilder::new();
+    let room = ptb.object(ObjectArg::SharedObject {
+        id: room,
+        initial_shared_version: room_initial_version(sui, room).await?,
+        mutable: true,
+    })?;
+    ptb.command(Command::MoveCall(Box::new(ProgrammableMoveCall {
+        package: deployment.package,
+        module: Identifier::new("gamecards")?,
+        function: Identifier::new(function)?,
+        type_arguments: vec![],
+        arguments: vec![room],
+    })));
+    Ok(ptb.finish())
+}
+
+/// Whether a parameter is the `TxContext` that Move entry functions may take last.
+fn is_tx_context(parameter: &SuiMoveNormalizedType) -> bool {
+    let (SuiMoveNormalizedType::Reference(inner) | SuiMoveNormalizedType::MutableReference(inner)) =
+        parameter
+    else {
+        return false;
+    };
+    matches!(
+        inner.as_ref(),
+        SuiMoveNormalizedType::Struct { address, module, name, .. }
+            if ObjectID::from_hex_literal(address).ok() == Some(ObjectID::from_single_byte(2))
+                && module == "tx_context"
+                && name == "TxContext"
+    )
+}
+
+/// Build a programmable transaction that sends `amount` MIST to `recipient`.
+///
+/// The amount is split from the `source` coins, which are merged first if there are several,
+/// or from the gas coin if `source` is empty.
+pub fn transfer_sui_ptb(
+    recipient: SuiAddress,
+    amount: u64,
+    source: &[ObjectRef],
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut ptb = PtbBuilder::new();
+    let Some((first, rest)) = source.split_first() else {
+        ptb.pay_sui(vec![recipient], vec![amount])?;
+        return Ok(ptb.finish());
+    };
+
+    let coin = ptb.object(ObjectArg::ImmOrOwnedObject(*first))?;
+    if !rest.is_empty() {
+        let others = rest
+            .iter()
+            .map(|other| ptb.object(ObjectArg::ImmOrOwnedObject(*other)))
+            .collect::<Result<_, _>>()?;
+        ptb.command(Command::MergeCoins(coin, others));
+    }
+    let amount = ptb.pure(amount)?;
+    let Argument::Result(split) = ptb.command(Command::SplitCoins(coin, vec![amount])) else {
+        unreachable!("commands always produce a result argument")
+    };
+    ptb.transfer_args(recipient, vec![Argument::NestedResult(split, 0)]);
+    Ok(ptb.finish())
+}
+
+/// Pick the coin of the sender that pays for gas, which must hold at least `required` MIST,
+/// or `required` units of `coin_type` if gas is paid with another coin.
+///
+/// See [`gas::select_gas_coins`] for how the coin is chosen.
+pub async fn gas_coin(
+    sui: &SuiClient,
+    sender: SuiAddress,
+    required: u64,
+    coin_type: Option<&StructTag>,
+) -> Result<ObjectRef, anyhow::Error> {
+    let selection = gas::select_gas_coins(sui, sender, required, coin_type).await?;
+    if selection.needs_merge() {
+        bail!(
+            "No single coin of {sender} holds {}; merge {} coins first",
+            gas::format_gas(required, coin_type),
+            selection.coins.len()
+        );
+    }
+    Ok(selection.coins[0].object_ref())
+}
+
+/// How long to keep looking for a transaction after the full node timed out waiting
+/// for its local execution.
+pub const DEFAULT_CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Signature schemes of the keys held in a Sui keystore.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum KeyScheme {
+    Ed25519,
+    Secp256k1,
+    Secp256r1,
+}
+
+impl KeyScheme {
+    pub fn signature_scheme(self) -> SignatureScheme {
+        match self {
+            KeyScheme::Ed25519 => SignatureScheme::ED25519,
+            KeyScheme::Secp256k1 => SignatureScheme::Secp256k1,
+            KeyScheme::Secp256r1 => SignatureScheme::Secp256r1,
+        }
+    }
+}
+
+/// Sign the transaction with the sender's key from the local keystore.
+///
+/// With `key_scheme`, the key stored for the sender must use that scheme, so that a
+/// keystore holding unexpected entries fails here instead of producing a signature the
+/// network rejects.
+///
+/// The keystore file can be briefly unreadable while the `sui` CLI or another process
+/// rewrites it, so reading it is retried a few times. A missing key fails immediately.
+#[instrument(skip_all, fields(sender = %sender))]
+pub async fn sign(
+    sender: SuiAddress,
+    tx_data: TransactionData,
+    key_scheme: Option<KeyScheme>,
+) -> Result<Transaction, anyhow::Error> {
+    let mut attempt = 1;
+    loop {
+        match sign_once(sender, &tx_data, key_scheme) {
+            Err(e) if attempt < SIGN_ATTEMPTS && is_transient_keystore_error(&e) => {
+                warn!("Cannot read the keystore ({e:#}), retrying");
+                attempt += 1;
+                tokio::time::sleep(SIGN_RETRY_DELAY).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+const SIGN_ATTEMPTS: u32 = 3;
+const SIGN_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+fn sign_once(
+    sender: SuiAddress,
+    tx_data: &TransactionData,
+    key_scheme: Option<KeyScheme>,
+) -> Result<Transaction, anyhow::Error> {
+    let keystore = FileBasedKeystore::new(&sui_config_dir()?.join(SUI_KEYSTORE_FILENAME))?;
+    if let Some(key_scheme) = key_scheme {
+        let stored = keystore
+            .get_key(&sender)
+            .map_err(|e| anyhow!("{e}"))?
+            .public()
+            .scheme();
+        if stored != key_scheme.signature_scheme() {
+            bail!("The key stored for {sender} is a {stored:?} key, not the requested {key_scheme:?} key");
+        }
+    }
+    let signature = keystore
+        .sign_secure(&sender, tx_data, Intent::sui_transaction())
+        .map_err(|e| anyhow!("Cannot sign for {sender}: {e}"))?;
+    Ok(Transaction::from_data(tx_data.clone(), vec![signature]))
+}
+
+/// Whether a keystore error comes from reading the keystore file, which may succeed on a
+/// second try, as opposed to the keystore not holding the key, which never will.
+pub fn is_transient_keystore_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|e| e.kind() != std::io::ErrorKind::NotFound)
+            || cause.is::<serde_json::Error>()
+    })
+}
+
+/// Execute a signed transaction, waiting for the full node to apply its effects.
+///
+/// A busy full node can time out waiting for local execution even though the transaction
+/// was executed by the network. In that case the transaction is looked up by its digest
+/// for up to `confirm_timeout`, and its response is returned if it landed.
+#[instrument(skip_all, fields(digest = %transaction.digest()))]
+pub async fn execute(
+    sui: &SuiClient,
+    transaction: Transaction,
+    confirm_timeout: Duration,
+) -> Result<SuiTransactionBlockResponse, anyhow::Error> {
+    let digest = *transaction.digest();
+
+    let submitted = sui
+        .quorum_driver_api()
+        .execute_transaction_block(
+            transaction,
+            SuiTransactionBlockResponseOptions::full_content(),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await;
+    confirm_submission(
+        submitted,
+        digest,
+        confirm_timeout,
+        CONFIRM_POLL_INTERVAL,
+        || async {
+            let result = sui
+                .read_api()
+                .get_transaction_with_options(
+                    digest,
+                    SuiTransactionBlockResponseOptions::full_content(),
+                )
+                .await
+                .map_err(anyhow::Error::from);
+            transcript::record_rpc(
+                "sui_getTransactionBlock",
+                || json!({ "digest": digest }),
+                result.as_ref(),
+            );
+            result
+        },
+    )
+    .await
+}
+
+/// Error of a full node whose quorum driver gave up waiting for the transaction's effects.
+const FINALITY_TIMEOUT_MESSAGE: &str = "Transaction timed out before reaching finality";
+
+/// Turn the outcome of submitting transaction `digest` into its response.
+///
+/// If the submission failed in a way that leaves the transaction possibly executed (see
+/// [`is_unconfirmed_submission`]), `lookup` is called every `poll_interval` for up to
+/// `confirm_timeout` and its first response is returned. Any other error is returned as is,
+/// since the transaction cannot have landed.
+pub async fn confirm_submission<F, Fut>(
+    submitted: Result<SuiTransactionBlockResponse, sui_sdk::error::Error>,
+    digest: TransactionDigest,
+    confirm_timeout: Duration,
+    poll_interval: Duration,
+    lookup: F,
+) -> Result<SuiTransactionBlockResponse, anyhow::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<SuiTransactionBlockResponse, anyhow::Error>>,
+{
+    match submitted {
+        Ok(response) => Ok(response),
+        Err(e) if is_unconfirmed_submission(&e) => {
+            warn!("Timed out waiting for local execution, checking whether {digest} landed");
+            match poll_transaction(lookup, confirm_timeout, poll_interval).await {
+                Some(response) => {
+                    info!("Transaction {digest} landed");
+                    Ok(response)
+                }
+                None => bail!(
+                    "Transaction {digest} was submitted but could not be confirmed within {}s: {e}",
+                    confirm_timeout.as_secs()
+                ),
+            }
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Whether a submission error means the transaction reached the network but its effects
+/// were not confirmed in time, so it may still have executed.
+///
+/// Only the SDK's own wait for local execution and the full node's finality timeout qualify;
+/// other timeouts, such as failing to connect, happen before the transaction is submitted.
+pub fn is_unconfirmed_submission(error: &sui_sdk::error::Error) -> bool {
+    matches!(
+        error,
+        sui_sdk::error::Error::FailToConfirmTransactionStatus(..)
+    ) || error.to_string().contains(FINALITY_TIMEOUT_MESSAGE)
+}
+
+async fn poll_transaction<F, Fut>(
+    mut lookup: F,
+    timeout: Duration,
+    interval: Duration,
+) -> Option<SuiTransactionBlockResponse>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<SuiTransactionBlockResponse, anyhow::Error>>,
+{
+    let start = Instant::now();
+    loop {
+        if let Ok(response) = lookup().await {
+            return Some(response);
+        }
+        if start.elapsed() >= timeout {
+            return None;
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
@@ -0,0 +1,361 @@
+use anyhow::bail;
+use move_core_types::language_storage::StructTag;
+use serde_json::json;
+use sui_json_rpc_types::{
+    Coin, SuiExecutionStatus, SuiProtocolConfigValue, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse,
+};
+use sui_sdk::{
+    types::{
+        base_types::SuiAddress, gas::GasCostSummary, gas_coin::GAS, transaction::TransactionData,
+    },
+    SuiClient,
+};
+use tracing::warn;
+
+use crate::transcript;
+
+/// Number of MIST in one SUI.
+pub const MIST_PER_SUI: u64 = 1_000_000_000;
+
+/// Gas budget used for a single transaction when none is given, in MIST.
+pub const DEFAULT_GAS_BUDGET: u64 = 10_000_000;
+
+/// How a transaction pays for gas.
+#[derive(Clone, Copy, Debug)]
+pub struct GasSettings {
+    /// Maximum amount the transaction may spend on gas, in MIST. When `estimate_basis` is
+    /// set, this is only the budget of the dry run that estimates the real budget.
+    pub budget: u64,
+    /// Percentage added on top of the reference gas price.
+    pub price_margin_pct: u64,
+    /// Derive the budget of each transaction from a dry run instead of using `budget`.
+    pub estimate_basis: Option<EstimateBasis>,
+}
+
+/// Percentage added on top of an estimated cost to get the gas budget.
+pub const ESTIMATE_BUFFER_PCT: u64 = 20;
+
+/// Which parts of a dry run's gas summary a gas budget is estimated from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum EstimateBasis {
+    /// The larger of the computation cost and the net cost (computation + storage - rebate),
+    /// which is always enough for the transaction to run
+    #[default]
+    Total,
+    /// The computation cost only. Storage is charged on top of it, so this budget is too low
+    /// for transactions that store more than they free
+    Computation,
+    /// The computation and storage costs, ignoring the storage rebate
+    ComputationPlusStorage,
+}
+
+impl EstimateBasis {
+    /// The gas budget for a transaction with this gas summary, with the estimate buffer.
+    pub fn budget(self, summary: &GasCostSummary) -> u64 {
+        let cost = match self {
+            EstimateBasis::Total => {
+                let net = u64::try_from(summary.net_gas_usage()).unwrap_or(0);
+                summary.computation_cost.max(net)
+            }
+            EstimateBasis::Computation => summary.computation_cost,
+            EstimateBasis::ComputationPlusStorage => summary
+                .computation_cost
+                .saturating_add(summary.storage_cost),
+        };
+        cost.saturating_add(cost.saturating_mul(ESTIMATE_BUFFER_PCT).div_ceil(100))
+    }
+}
+
+/// Refuse a gas budget above `max`, or only warn about it if `allow` is set.
+///
+/// The budget is the most a transaction can spend on gas, so a budget with a few zeros too
+/// many is caught here rather than paid for.
+pub fn check_budget(budget: u64, max: u64, allow: bool) -> Result<(), anyhow::Error> {
+    if budget <= max {
+        return Ok(());
+    }
+    if !allow {
+        bail!(
+            "The gas budget of {} is above the limit of {}; pass --allow-high-budget to use it anyway",
+            format_sui(budget),
+            format_sui(max)
+        );
+    }
+    warn!(
+        "Using a gas budget of {}, above the limit of {}",
+        format_sui(budget),
+        format_sui(max)
+    );
+    Ok(())
+}
+
+/// The largest gas budget the network accepts for a transaction, in MIST, from the
+/// `max_tx_gas` setting of its current protocol version.
+pub async fn max_tx_gas(sui: &SuiClient) -> Result<u64, anyhow::Error> {
+    let config = sui.read_api().get_protocol_config(None).await?;
+    match config.attributes.get("max_tx_gas") {
+        Some(Some(SuiProtocolConfigValue::U64(max))) => Ok(*max),
+        other => bail!(
+            "Protocol version {} has no max_tx_gas setting (found {other:?})",
+            config.protocol_version.as_u64()
+        ),
+    }
+}
+
+/// Refuse a gas budget above the protocol's `max_tx_gas`, which the network would reject.
+pub fn check_protocol_budget(budget: u64, max_tx_gas: u64) -> Result<(), anyhow::Error> {
+    if budget > max_tx_gas {
+        bail!(
+            "The gas budget of {} is above the network's maximum of {} (max_tx_gas)",
+            format_sui(budget),
+            format_sui(max_tx_gas)
+        );
+    }
+    Ok(())
+}
+
+/// Fetch the reference gas price and raise it by `margin_pct` percent, rounding up.
+///
+/// Paying more than the reference price only raises the tip paid to validators for
+/// each unit of gas; the total spent can never exceed the gas budget.
+pub async fn gas_price(sui: &SuiClient, margin_pct: u64) -> Result<u64, anyhow::Error> {
+    let result = sui
+        .read_api()
+        .get_reference_gas_price()
+        .await
+        .map_err(anyhow::Error::from);
+    transcript::record_rpc("suix_getReferenceGasPrice", || json!({}), result.as_ref());
+    Ok(with_margin(result?, margin_pct))
+}
+
+fn with_margin(price: u64, margin_pct: u64) -> u64 {
+    price.saturating_add(price.saturating_mul(margin_pct).div_ceil(100))
+}
+
+/// Format an amount of MIST as a decimal SUI amount, e.g. `0.001997880 SUI`.
+pub fn format_sui(mist: impl Into<i128>) -> String {
+    let mist: i128 = mist.into();
+    let sign = if mist < 0 { "-" } else { "" };
+    let mist = mist.unsigned_abs();
+    format!(
+        "{sign}{}.{:09} SUI",
+        mist / MIST_PER_SUI as u128,
+        mist % MIST_PER_SUI as u128
+    )
+}
+
+/// Outcome of dry-running a transaction: what it would cost and whether it would succeed.
+pub struct GasEstimate {
+    pub summary: GasCostSummary,
+    /// `None` if the transaction would succeed, otherwise the execution error.
+    pub error: Option<String>,
+}
+
+impl GasEstimate {
+    /// Net cost of the transaction in MIST (computation + storage - storage rebate).
+    pub fn net_cost(&self) -> i64 {
+        self.summary.net_gas_usage()
+    }
+}
+
+/// Gas spent by a set of executed transactions, in MIST.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasReport {
+    /// Transactions whose gas summary was counted.
+    pub transactions: usize,
+    /// Transactions returned without effects, which are left out of the totals.
+    pub missing_effects: usize,
+    pub computation_cost: u128,
+    pub storage_cost: u128,
+    pub storage_rebate: u128,
+}
+
+impl GasReport {
+    /// Aggregate the gas summaries of transactions fetched with their effects.
+    pub fn from_transactions(transactions: &[SuiTransactionBlockResponse]) -> Self {
+        let mut report = Self::default();
+        for transaction in transactions {
+            report.add(
+                transaction
+                    .effects
+                    .as_ref()
+                    .map(|effects| effects.gas_cost_summary()),
+            );
+        }
+        report
+    }
+
+    /// Count one transaction, or one without effects if `summary` is `None`.
+    pub fn add(&mut self, summary: Option<&GasCostSummary>) {
+        let Some(summary) = summary else {
+            self.missing_effects += 1;
+            return;
+        };
+        self.transactions += 1;
+        self.computation_cost += u128::from(summary.computation_cost);
+        self.storage_cost += u128::from(summary.storage_cost);
+        self.storage_rebate += u128::from(summary.storage_rebate);
+    }
+
+    /// Total spent net of rebates (computation + storage - storage rebate), which is
+    /// negative if the transactions freed more storage than they used.
+    pub fn net_cost(&self) -> i128 {
+        (self.computation_cost + self.storage_cost) as i128 - self.storage_rebate as i128
+    }
+
+    /// Average net cost per counted transaction, or `None` if none were counted.
+    pub fn average_net_cost(&self) -> Option<i128> {
+        (self.transactions > 0).then(|| self.net_cost() / self.transactions as i128)
+    }
+}
+
+/// Dry-run the transaction against the current state of the network.
+/// Nothing is signed or executed.
+pub async fn dry_run(
+    sui: &SuiClient,
+    tx_data: TransactionData,
+) -> Result<GasEstimate, anyhow::Error> {
+    let result = sui
+        .read_api()
+        .dry_run_transaction_block(tx_data.clone())
+        .await
+        .map_err(anyhow::Error::from)
+        .map(|response| {
+            let effects = response.effects;
+            let error = match effects.status() {
+                SuiExecutionStatus::Success => None,
+                SuiExecutionStatus::Failure { error } => Some(error.clone()),
+            };
+            GasEstimate {
+                summary: effects.gas_cost_summary().clone(),
+                error,
+            }
+        });
+    transcript::record_rpc(
+        "sui_dryRunTransactionBlock",
+        || json!({ "transaction": tx_data }),
+        result
+            .as_ref()
+            .map(|estimate| json!({ "summary": estimate.summary, "error": estimate.error })),
+    );
+    result
+}
+
+/// The SUI coins chosen to cover an amount of gas.
+pub struct GasSelection {
+    /// The amount the coins must cover, in MIST.
+    pub required: u64,
+    /// The chosen coins, largest first when more than one is needed.
+    pub coins: Vec<Coin>,
+}
+
+impl GasSelection {
+    /// Whether no single coin covers the amount, so the coins have to be merged first.
+    pub fn needs_merge(&self) -> bool {
+        self.coins.len() > 1
+    }
+
+    /// Total balance of the chosen coins, in MIST.
+    pub fn total(&self) -> u64 {
+        self.coins.iter().map(|coin| coin.balance).sum()
+    }
+}
+
+/// Choose the coins of `owner` that pay for `required` units of gas: SUI coins, or coins of
+/// `coin_type` on a chain that accepts another coin as gas.
+///
+/// See [`select_coins`] for how the coins are chosen. Fails if all the coins of `owner`
+/// together are not enough.
+pub async fn select_gas_coins(
+    sui: &SuiClient,
+    owner: SuiAddress,
+    required: u64,
+    coin_type: Option<&StructTag>,
+) -> Result<GasSelection, anyhow::Error> {
+    let coins = coins_of_type(sui, owner, coin_type).await?;
+    if coins.is_empty() {
+        bail!("Address {owner} has no coins to pay for gas");
+    }
+    let total: u64 = coins.iter().map(|coin| coin.balance).sum();
+    match select_coins(coins, required) {
+        Some(coins) => Ok(GasSelection { required, coins }),
+        None => bail!(
+            "Address {owner} only holds {} in total, less than the {} needed",
+            format_gas(total, coin_type),
+            format_gas(required, coin_type)
+        ),
+    }
+}
+
+/// Format an amount of gas as SUI, or in raw units of another gas coin type.
+pub fn format_gas(amount: u64, coin_type: Option<&StructTag>) -> String {
+    match coin_type {
+        Some(coin_type) => format!("{amount} units of {coin_type}"),
+        None => format_sui(amount),
+    }
+}
+
+/// The coin type to pay gas with: `None` for SUI, even when `coin_type` names it.
+pub fn non_sui_gas_type(coin_type: Option<StructTag>) -> Option<StructTag> {
+    coin_type.filter(|coin_type| *coin_type != GAS::type_())
+}
+
+/// Choose coins from `coins` holding at least `required` MIST together, or `None` if all of
+/// them are not enough.
+///
+/// The smallest coin that covers the amount on its own is preferred, which keeps large coins
+/// whole. When no coin is large enough, the largest coins are taken, largest first, until
+/// their total covers the amount.
+pub fn select_coins(mut coins: Vec<Coin>, required: u64) -> Option<Vec<Coin>> {
+    coins.sort_by_key(|coin| coin.balance);
+    if let Some(i) = coins.iter().position(|coin| coin.balance >= required) {
+        return Some(vec![coins.swap_remove(i)]);
+    }
+
+    let mut selected = vec![];
+    let mut total = 0u64;
+    for coin in coins.into_iter().rev() {
+        total = total.saturating_add(coin.balance);
+        selected.push(coin);
+        if total >= required {
+            return Some(selected);
+        }
+    }
+    None
+}
+
+/// Every SUI coin owned by an address, fetched page by page.
+pub async fn sui_coins(sui: &SuiClient, owner: SuiAddress) -> Result<Vec<Coin>, anyhow::Error> {
+    coins_of_type(sui, owner, None).await
+}
+
+/// Every coin of `coin_type`, or SUI if `None`, owned by an address, fetched page by page.
+pub async fn coins_of_type(
+    sui: &SuiClient,
+    owner: SuiAddress,
+    coin_type: Option<&StructTag>,
+) -> Result<Vec<Coin>, anyhow::Error> {
+    let coin_type = coin_type.map(ToString::to_string);
+    let mut coins = vec![];
+    let mut cursor = None;
+    loop {
+        let page = sui
+            .coin_read_api()
+            .get_coins(owner, coin_type.clone(), cursor, None)
+            .await
+            .map_err(anyhow::Error::from);
+        transcript::record_rpc(
+            "suix_getCoins",
+            || json!({ "owner": owner, "coin_type": coin_type, "cursor": cursor }),
+            page.as_ref(),
+        );
+        let page = page?;
+        coins.extend(page.data);
+        if !page.has_next_page {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    Ok(coins)
+}
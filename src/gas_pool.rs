@@ -0,0 +1,184 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail};
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::Coin;
+use sui_sdk::{
+    types::{
+        base_types::{ObjectID, ObjectRef, SuiAddress},
+        gas::GasCostSummary,
+    },
+    SuiClient,
+};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::gas;
+
+/// A fixed set of gas coins whose object refs are kept current for a long-lived client.
+///
+/// A [`crate::GameClient`] using the pool pays for gas with its coins and updates their refs
+/// and balances from the effects of its own transactions. Other processes may still use the
+/// same coins, so [`GasPool::refresh`], or a task started with [`GasPool::spawn_refresh`],
+/// re-reads them from the chain. Clones share the same coins.
+///
+/// Coins are handed out without being reserved, so a pool and its clones must only have one
+/// transaction in flight at a time; two transactions planned at once would pay with the same
+/// coin, and one of them would fail. Concurrent senders each need a pool of their own, as in
+/// [`crate::load::create_rooms_at_rate`].
+#[derive(Clone)]
+pub struct GasPool {
+    owner: SuiAddress,
+    /// Type of the pool's coins, `None` for SUI.
+    coin_type: Option<StructTag>,
+    coins: Arc<Mutex<Vec<Coin>>>,
+}
+
+impl GasPool {
+    /// A pool of the given coins of `owner`, which must all exist and be of `coin_type`, or
+    /// SUI if it is `None`.
+    pub async fn new(
+        sui: &SuiClient,
+        owner: SuiAddress,
+        coin_ids: Vec<ObjectID>,
+        coin_type: Option<StructTag>,
+    ) -> Result<Self, anyhow::Error> {
+        let coin_type = gas::non_sui_gas_type(coin_type);
+        let owned = gas::coins_of_type(sui, owner, coin_type.as_ref()).await?;
+        let mut coins = Vec::with_capacity(coin_ids.len());
+        for id in coin_ids {
+            let coin = owned
+                .iter()
+                .find(|coin| coin.coin_object_id == id)
+                .ok_or_else(|| {
+                    let coin_type = coin_type.as_ref().map_or("SUI".into(), ToString::to_string);
+                    anyhow!("{id} is not a {coin_type} coin owned by {owner}")
+                })?;
+            coins.push(coin.clone());
+        }
+        Ok(Self {
+            owner,
+            coin_type,
+            coins: Arc::new(Mutex::new(coins)),
+        })
+    }
+
+    pub fn owner(&self) -> SuiAddress {
+        self.owner
+    }
+
+    /// The type of the pool's coins, or `None` for SUI.
+    pub fn coin_type(&self) -> Option<&StructTag> {
+        self.coin_type.as_ref()
+    }
+
+    /// The coins of the pool as last seen.
+    pub fn coins(&self) -> Vec<Coin> {
+        self.coins.lock().unwrap().clone()
+    }
+
+    /// The smallest coin of the pool holding at least `required` MIST, or units of the
+    /// pool's coin type.
+    pub fn coin(&self, required: u64) -> Result<ObjectRef, anyhow::Error> {
+        self.coins
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|coin| coin.balance >= required)
+            .min_by_key(|coin| coin.balance)
+            .map(Coin::object_ref)
+            .ok_or_else(|| {
+                anyhow!(
+                    "No coin of the gas pool holds {}",
+                    gas::format_gas(required, self.coin_type.as_ref())
+                )
+            })
+    }
+
+    /// Record the new ref and balance of a coin after a transaction used it for gas,
+    /// charging it the net gas cost and the `sent` MIST split from it.
+    pub fn update(&self, gas_object: ObjectRef, gas_used: &GasCostSummary, sent: u64) {
+        let (id, version, digest) = gas_object;
+        if let Some(coin) = self
+            .coins
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|coin| coin.coin_object_id == id)
+        {
+            coin.version = version;
+            coin.digest = digest;
+            let spent = i128::from(gas_used.net_gas_usage()) + i128::from(sent);
+            coin.balance = u64::try_from(i128::from(coin.balance) - spent).unwrap_or(0);
+        }
+    }
+
+    /// Re-read the refs and balances of the pool's coins from the chain.
+    ///
+    /// Coins that the owner no longer has, because they were merged or sent away, are
+    /// dropped from the pool with a warning.
+    pub async fn refresh(&self, sui: &SuiClient) -> Result<(), anyhow::Error> {
+        let owned = gas::coins_of_type(sui, self.owner, self.coin_type.as_ref()).await?;
+        let mut coins = self.coins.lock().unwrap();
+        coins.retain_mut(|coin| {
+            match owned
+                .iter()
+                .find(|c| c.coin_object_id == coin.coin_object_id)
+            {
+                Some(current) => {
+                    *coin = current.clone();
+                    true
+                }
+                None => {
+                    warn!(
+                        "Gas coin {} is gone, removing it from the pool",
+                        coin.coin_object_id
+                    );
+                    false
+                }
+            }
+        });
+        if coins.is_empty() {
+            bail!(
+                "None of the gas pool's coins is owned by {} any more",
+                self.owner
+            );
+        }
+        Ok(())
+    }
+
+    /// Refresh the pool every `interval` in a background task until the returned handle is
+    /// cancelled or dropped. Failed refreshes are logged and retried at the next tick.
+    pub fn spawn_refresh(&self, sui: SuiClient, interval: Duration) -> RefreshTask {
+        let pool = self.clone();
+        let handle = tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            loop {
+                ticks.tick().await;
+                if let Err(e) = pool.refresh(&sui).await {
+                    warn!("Cannot refresh the gas pool: {e}");
+                }
+            }
+        });
+        RefreshTask { handle }
+    }
+}
+
+/// Handle of a background gas pool refresh; the task stops when the handle is dropped.
+pub struct RefreshTask {
+    handle: JoinHandle<()>,
+}
+
+impl RefreshTask {
+    /// Stop refreshing the pool.
+    pub fn cancel(self) {}
+}
+
+impl Drop for RefreshTask {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
@@ -0,0 +1,36 @@
+//! Plan and execute transactions against the `gamecards` Move package on Sui.
+//!
+//! The `sui-connect` binary is a thin command line front end over [`GameClient`].
+
+pub mod assertions;
+pub mod batch;
+pub mod client;
+pub mod compare;
+pub mod config;
+pub mod created;
+pub mod decode;
+pub mod dynamic_fields;
+pub mod error;
+pub mod events;
+pub mod game;
+pub mod gas;
+pub mod gas_pool;
+pub mod load;
+pub mod modules;
+pub mod names;
+pub mod ping;
+pub mod plan;
+pub mod portfolio;
+pub mod ptb;
+pub mod pure;
+pub mod retry;
+pub mod script;
+pub mod sweep;
+#[cfg(feature = "tracing-export")]
+pub mod telemetry;
+pub mod transcript;
+pub mod utils;
+
+pub use client::{Action, GameClient};
+pub use error::GameError;
+pub use plan::TransactionPlan;
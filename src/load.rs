@@ -0,0 +1,143 @@
+use std::{sync::Mutex, time::Duration};
+
+use anyhow::bail;
+use futures::{stream, StreamExt};
+use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_sdk::types::base_types::ObjectID;
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::warn;
+
+use crate::client::{Action, GameClient};
+
+/// Outcome of one transaction of a load test.
+#[derive(Clone, Copy, Debug)]
+pub struct Sample {
+    /// Time from planning the transaction to its response.
+    pub latency: Duration,
+    pub success: bool,
+}
+
+/// Throughput, success rate and latencies of a load test.
+#[derive(Clone, Debug)]
+pub struct LoadReport {
+    pub elapsed: Duration,
+    pub submitted: usize,
+    pub succeeded: usize,
+    /// Latency of every transaction, successful or not, shortest first.
+    pub latencies: Vec<Duration>,
+}
+
+impl LoadReport {
+    pub fn new(samples: &[Sample], elapsed: Duration) -> Self {
+        let mut latencies: Vec<_> = samples.iter().map(|sample| sample.latency).collect();
+        latencies.sort();
+        Self {
+            elapsed,
+            submitted: samples.len(),
+            succeeded: samples.iter().filter(|sample| sample.success).count(),
+            latencies,
+        }
+    }
+
+    /// Successful transactions per second over the whole test.
+    pub fn achieved_tps(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            secs if secs > 0.0 => self.succeeded as f64 / secs,
+            _ => 0.0,
+        }
+    }
+
+    /// Share of the submitted transactions that succeeded, from 0 to 1.
+    pub fn success_rate(&self) -> f64 {
+        match self.submitted {
+            0 => 0.0,
+            submitted => self.succeeded as f64 / submitted as f64,
+        }
+    }
+
+    /// The latency that `pct` percent of the transactions did not exceed (nearest rank), or
+    /// `None` if nothing was submitted.
+    pub fn percentile(&self, pct: f64) -> Option<Duration> {
+        let rank = (pct / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        self.latencies
+            .get(rank.clamp(1, self.latencies.len().max(1)) - 1)
+            .copied()
+    }
+}
+
+/// Submit `create_room` transactions at `rate` per second for `duration`, one card per
+/// transaction, and report how they fared.
+///
+/// Each client has at most one transaction in flight, so the clients should pay for gas from
+/// distinct gas pools; their number bounds the concurrency. When every client is busy, new
+/// transactions wait instead of queuing up, and the ticks missed meanwhile are skipped, so a
+/// network that cannot keep up shows as an achieved rate below `rate`. The test ends early
+/// when the cards run out.
+pub async fn create_rooms_at_rate(
+    clients: &[GameClient],
+    cards: &[ObjectID],
+    rate: f64,
+    duration: Duration,
+) -> Result<LoadReport, anyhow::Error> {
+    if clients.is_empty() {
+        bail!("A load test needs at least one client");
+    }
+    if !(rate > 0.0 && rate.is_finite()) {
+        bail!("The rate must be a positive number of transactions per second, got {rate}");
+    }
+
+    let free = Mutex::new((0..clients.len()).collect::<Vec<_>>());
+    let mut ticks = tokio::time::interval(Duration::from_secs_f64(1.0 / rate));
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let start = Instant::now();
+    let deadline = start + duration;
+
+    let arrivals = stream::unfold((ticks, cards.iter()), |(mut ticks, mut cards)| async move {
+        ticks.tick().await;
+        if Instant::now() >= deadline {
+            return None;
+        }
+        let card = *cards.next()?;
+        Some((card, (ticks, cards)))
+    });
+    let samples: Vec<Sample> = arrivals
+        .map(|card| {
+            let free = &free;
+            async move {
+                let slot = free
+                    .lock()
+                    .unwrap()
+                    .pop()
+                    .expect("at most one transaction per client is in flight");
+                let started = Instant::now();
+                let outcome = clients[slot]
+                    .execute(&Action::CreateRoom {
+                        card,
+                        extra_objects: vec![],
+                        room_owner: None,
+                    })
+                    .await;
+                let latency = started.elapsed();
+                free.lock().unwrap().push(slot);
+
+                let success = match outcome {
+                    Ok(response) => match response.effects.as_ref().map(|e| e.status()) {
+                        Some(SuiExecutionStatus::Success) => true,
+                        status => {
+                            warn!("create_room with card {card} did not succeed: {status:?}");
+                            false
+                        }
+                    },
+                    Err(e) => {
+                        warn!("create_room with card {card} failed: {e:#}");
+                        false
+                    }
+                };
+                Sample { latency, success }
+            }
+        })
+        .buffer_unordered(clients.len())
+        .collect()
+        .await;
+    Ok(LoadReport::new(&samples, start.elapsed()))
+}
@@ -1,173 +1,1813 @@
 // Import necessary modules and libraries
-mod utils;
-use std::str::FromStr;
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
 
-use shared_crypto::intent::Intent;
-use sui_config::{sui_config_dir, SUI_KEYSTORE_FILENAME};
-use sui_json_rpc_types::{SuiObjectDataOptions, SuiObjectResponse};
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
+use anyhow::Context;
+use base64::Engine;
+use clap::{Args, Parser, Subcommand};
+use futures::future::LocalBoxFuture;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use sui_connect::{
+    assertions::{Assertions, CreatedType, ExpectedStatus},
+    batch,
+    compare::{self, Strategy},
+    config::{self, Config},
+    created::{self, IdsFormat},
+    decode::{self, MoveValueDecoder},
+    dynamic_fields,
+    events::{self, EventDecoder, EventFormat},
+    game::{self, CardAssignment, KeyScheme},
+    gas::{self, format_sui, EstimateBasis, GasReport, GasSettings},
+    gas_pool::GasPool,
+    load,
+    modules::ModuleCache,
+    names::{NameResolver, Recipient},
+    ping, plan,
+    portfolio::{self, HttpPriceSource},
+    pure,
+    script::{self, Bindings, ScriptLine},
+    sweep::{self, ArgRange},
+    transcript::{self, Transcript},
+    utils::{self, address_or_active, Network},
+    Action, GameClient,
+};
+use sui_json_rpc_types::{
+    EventFilter, ObjectChange, SuiExecutionStatus, SuiObjectDataFilter, SuiParsedData,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
 use sui_sdk::{
-    rpc_types::SuiTransactionBlockResponseOptions,
     types::{
-        base_types::{ObjectID, ObjectRef, SequenceNumber}, 
-        digests::{self, Digest, ObjectDigest}, 
-        object, 
-        programmable_transaction_builder::ProgrammableTransactionBuilder, 
-        quorum_driver_types::ExecuteTransactionRequestType, 
-        sui_serde::SuiStructTag, 
-        transaction::{Argument, CallArg, Command, ObjectArg, Transaction, TransactionData}, 
-        Identifier, 
-        TypeTag
-    }, 
-    SuiClient, 
-    SuiClientBuilder,
+        base_types::{ObjectID, SequenceNumber, SuiAddress},
+        digests::{ObjectDigest, TransactionDigest},
+        dynamic_field::DynamicFieldType,
+        object::Owner,
+        parse_sui_struct_tag, parse_sui_type_tag,
+        transaction::{ObjectArg, ProgrammableTransaction, TransactionKind},
+    },
+    SuiClient,
 };
-use utils::setup_for_write;
-
-// This example demonstrates how to use programmable transactions to chain multiple
-// actions into one transaction. The steps are as follows:
-// 1) Retrieve two addresses from the local wallet.
-// 2) Find a coin from the active address that contains Sui.
-// 3) Split the coin into one coin of 1000 MIST and the remaining balance.
-// 4) Transfer the split coin to the second Sui address.
+
+// This program demonstrates how to use programmable transactions to call into the
+// `gamecards` Move package on the Sui testnet. Creating a room works as follows:
+// 1) Retrieve the active address from the local wallet.
+// 2) Fetch the shared game room and the game card owned by the active address.
+// 3) Wrap the card in a Move vector and call `gamecards::create_room` with the room.
+// 4) Pay for the transaction with the smallest coin of the sender that covers the gas
+//    budget, or with its largest coins together when no single coin does.
 // 5) Sign the transaction.
 // 6) Execute the transaction.
-// The program prints output for some of these actions.
-// Finally, it prints the number of coins for the recipient address at the end.
-// Running this program multiple times should show an increasing number of coins for the recipient address.
+// Running the program without a subcommand creates a room from the default card; the
+// `quickstart` subcommand walks through the same flow and explains each step.
+
+#[derive(Parser)]
+#[command(about = "Create and manage gamecards rooms on Sui")]
+struct Cli {
+    #[command(flatten)]
+    global: GlobalArgs,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+/// Options shared by every subcommand.
+#[derive(Args)]
+struct GlobalArgs {
+    /// TOML config file [default: game.toml if it exists]
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Network to connect to [default: the config file's network, or testnet]
+    #[arg(long, global = true, value_enum)]
+    network: Option<Network>,
+
+    /// JSON-RPC URL of the full node to use instead of the network's public full node
+    #[arg(long, global = true)]
+    rpc_url: Option<String>,
+
+    /// Gas budget for each transaction, in MIST
+    #[arg(long, global = true, default_value_t = gas::DEFAULT_GAS_BUDGET)]
+    gas_budget: u64,
+
+    /// Set the gas budget of each transaction from a dry run, using these parts of the gas
+    /// summary plus a 20% buffer; `--gas-budget` is then only the dry run's budget
+    #[arg(long, global = true, value_enum, num_args = 0..=1, default_missing_value = "total")]
+    estimate_basis: Option<EstimateBasis>,
+
+    /// Use a gas budget above the config file's `max_gas_budget` instead of refusing it.
+    /// Budgets above the network's `max_tx_gas` are always refused
+    #[arg(long, global = true, visible_alias = "yes")]
+    allow_high_budget: bool,
+
+    /// Pay this percentage above the reference gas price so the transaction still lands
+    /// if the price ticks up before submission [default: 5 on mainnet, 0 elsewhere].
+    /// A higher price only raises the tip per unit of gas; the total spent never exceeds
+    /// the gas budget
+    #[arg(long, global = true)]
+    gas_price_margin_pct: Option<u64>,
+
+    /// Pay for gas with coins of this type instead of SUI, on a chain that accepts another
+    /// gas coin; each transaction is dry-run first to check that it does [default: SUI]
+    #[arg(long, global = true, value_parser = parse_sui_struct_tag)]
+    gas_coin_type: Option<StructTag>,
+
+    /// When the full node times out waiting for local execution, keep polling for the
+    /// transaction for this many seconds before reporting it as unconfirmed
+    #[arg(long, global = true, default_value_t = game::DEFAULT_CONFIRM_TIMEOUT.as_secs())]
+    confirm_timeout_secs: u64,
+
+    /// Address or keystore alias that signs transactions [default: the config file's
+    /// `default_sender`, or the wallet's active address]
+    #[arg(long, global = true)]
+    sender: Option<String>,
+
+    /// When a shared object is congested, plan and submit the transaction again with a
+    /// backoff, as configured under `[rpc.congestion]`, instead of failing
+    #[arg(long, global = true)]
+    retry_congestion: bool,
+
+    /// Only sign if the sender's key in the keystore uses this scheme
+    #[arg(long, global = true, value_enum)]
+    key_scheme: Option<KeyScheme>,
+
+    /// Do not check that objects passed to Move calls have the expected type, or that the
+    /// card passed to `create_room` is owned by the sender
+    #[arg(long, global = true)]
+    no_type_check: bool,
+
+    /// After each transaction, fail unless it ended with this status
+    #[arg(long, global = true, value_enum)]
+    assert_status: Option<ExpectedStatus>,
+
+    /// After each transaction, fail unless it created objects of this type: `<type>` for
+    /// at least one, or `<type>=<count>` for exactly `count`. Can be repeated
+    #[arg(long, global = true)]
+    assert_created_type: Vec<CreatedType>,
+
+    /// Record the RPC calls made to query the game and to plan and submit transactions, with
+    /// secrets redacted, and the gas coin and budget decisions to this JSON lines file, e.g.
+    /// to attach to a bug report
+    #[arg(long, global = true)]
+    transcript: Option<PathBuf>,
+
+    /// Export tracing spans to this JSON lines file, to `otlp` (the collector in
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT`) or to an `http(s)://` OTLP collector
+    #[cfg(feature = "tracing-export")]
+    #[arg(long, global = true, env = "SUI_CONNECT_TRACE_OUT")]
+    trace_out: Option<sui_connect::telemetry::TraceTarget>,
+
+    /// Settings loaded from the config file
+    #[arg(skip)]
+    settings: Config,
+
+    /// The transcript opened from `--transcript`
+    #[arg(skip)]
+    transcript_file: Option<Transcript>,
+}
+
+impl GlobalArgs {
+    fn network(&self) -> Network {
+        self.network.or(self.settings.network).unwrap_or_default()
+    }
+
+    fn rpc_url(&self) -> &str {
+        self.rpc_url
+            .as_deref()
+            .unwrap_or_else(|| self.network().rpc_url())
+    }
+
+    async fn setup_for_read(&self) -> Result<SuiClient, anyhow::Error> {
+        utils::setup_for_read_at(self.network(), self.rpc_url()).await
+    }
+
+    /// Connect to the network with a wallet and pick the sender: `--sender`, then the
+    /// config file's `default_sender`, then the wallet's active address. The wallet is only
+    /// read, see `init-wallet` to create one.
+    async fn setup_for_write(&self) -> Result<(SuiClient, SuiAddress), anyhow::Error> {
+        let sender = match (&self.sender, &self.settings.default_sender) {
+            (Some(sender), _) => utils::keystore_address(sender)?,
+            (None, Some(sender)) => utils::keystore_address(sender)
+                .map_err(|e| e.context("Invalid default_sender in the config file"))?,
+            (None, None) => return utils::setup_sender_at(self.network(), self.rpc_url()).await,
+        };
+        Ok((self.setup_for_read().await?, sender))
+    }
+
+    /// The deployment from the config file, with `args` overriding it.
+    fn deployment(&self, args: &DeploymentArgs) -> game::Deployment {
+        let configured = self.settings.deployment();
+        game::Deployment {
+            package: args.package.unwrap_or(configured.package),
+            room: args.room.unwrap_or(configured.room),
+            card_type: args.card_type.clone().or(configured.card_type),
+            room_type: args.room_type.clone().or(configured.room_type),
+            room_ref: args
+                .room_initial_version
+                .map(|version| game::RoomRef {
+                    initial_shared_version: SequenceNumber::from_u64(version),
+                    mutable: args.room_mutable.unwrap_or(true),
+                })
+                .or(configured.room_ref),
+        }
+    }
+
+    fn gas_settings(&self) -> GasSettings {
+        GasSettings {
+            budget: self.gas_budget,
+            price_margin_pct: self
+                .gas_price_margin_pct
+                .unwrap_or_else(|| self.network().default_gas_price_margin_pct()),
+            estimate_basis: self.estimate_basis,
+        }
+    }
+
+    fn game_client(
+        &self,
+        sui: SuiClient,
+        sender: SuiAddress,
+        deployment: game::Deployment,
+    ) -> GameClient {
+        GameClient::new(sui, sender, deployment, self.gas_settings())
+            .with_budget_limit(self.settings.max_gas_budget, self.allow_high_budget)
+            .with_key_scheme(self.key_scheme)
+            .with_confirm_timeout(Duration::from_secs(self.confirm_timeout_secs))
+            .with_rpc_policies(self.settings.rpc)
+            .with_type_check(!self.no_type_check)
+            .with_congestion_retry(self.retry_congestion)
+            .with_gas_coin_type(self.gas_coin_type.clone())
+            .with_transcript(self.transcript_file.clone())
+    }
+
+    /// Fail with every `--assert-*` condition the transaction does not meet.
+    fn check_assertions(
+        &self,
+        response: &SuiTransactionBlockResponse,
+    ) -> Result<(), anyhow::Error> {
+        Assertions {
+            status: self.assert_status,
+            created: self.assert_created_type.clone(),
+        }
+        .check(response)
+    }
+
+    fn read_only_client(&self, sui: SuiClient, deployment: game::Deployment) -> GameClient {
+        GameClient::read_only(sui, deployment)
+            .with_rpc_policies(self.settings.rpc)
+            .with_transcript(self.transcript_file.clone())
+    }
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Create a room from a single game card (the default)
+    CreateRoom(CreateRoomArgs),
+    /// Create one room for each card listed in a file
+    BatchCreateRoom(BatchCreateRoomArgs),
+    /// Print an object, optionally as it was at a past version
+    ShowObject(ShowObjectArgs),
+    /// Transfer owned cards to their recipients in a single transaction
+    DistributeCards(DistributeCardsArgs),
+    /// Print the state of the network
+    Status,
+    /// Print the SUI balance of an address
+    Balance(AddressArgs),
+    /// List the `gamecards` objects owned by an address
+    ListCards(ListCardsArgs),
+    /// Print an executed transaction
+    Tx(TxArgs),
+    /// List the most recent transactions sent by an address
+    History(HistoryArgs),
+    /// Add up the gas spent by the most recent transactions sent by an address
+    GasReport(GasReportArgs),
+    /// List every coin type held by an address, optionally with an approximate USD total
+    Portfolio(PortfolioArgs),
+    /// Generate a new account in the local keystore, optionally funded from the faucet
+    NewAccount(NewAccountArgs),
+    /// Create a local wallet with two addresses if there is none, keeping any existing keys
+    /// and active address
+    InitWallet,
+    /// Send SUI to an address or a SuiNS name
+    Transfer(TransferArgs),
+    /// Write every object owned by an address, with full content, to a JSON file
+    Export(ExportArgs),
+    /// Print every event emitted by the game package as it happens
+    WatchPackage(WatchPackageArgs),
+    /// Check the setup end to end: connect, fund the sender if needed, create a room and
+    /// optionally join it
+    Quickstart(QuickstartArgs),
+    /// Dry-run `create_room` for each value of an integer argument and report the outcomes
+    Sweep(SweepArgs),
+    /// Create rooms at a fixed rate for a while and report throughput and latencies
+    Load(LoadArgs),
+    /// Show which coins would pay for a gas budget, without building a transaction
+    GasPreview(GasPreviewArgs),
+    /// Check whether each object of a list exists, was deleted or was never found
+    Exists(ExistsArgs),
+    /// Print the BCS bytes of a pure argument, to debug argument encoding
+    EncodeArg(EncodeArgArgs),
+    /// Measure the latency and success rate of RPC endpoints, best first
+    PingRpc(PingRpcArgs),
+    /// Dry-run `create_room` built two ways and compare their gas costs
+    CompareGas(CompareGasArgs),
+    /// List the dynamic fields of an object with their names and values decoded
+    DynamicFields(DynamicFieldsArgs),
+    /// Fetch objects of one Move type and decode them all into a JSON array
+    DecodeMany(DecodeManyArgs),
+    /// Run commands of this tool from a file, one per line, in order
+    Script(ScriptArgs),
+    /// Check a config file and report every problem in it, without running anything
+    ValidateConfig(ValidateConfigArgs),
+    /// Print the steps and commands a transaction builder generates, without executing it
+    #[command(subcommand)]
+    ShowBuilder(ShowBuilderCommands),
+}
+
+#[derive(Subcommand)]
+enum ShowBuilderCommands {
+    /// The builder of `create-room`
+    CreateRoom(ShowCreateRoomArgs),
+}
+
+/// Object IDs of the `gamecards` deployment; the config file or the testnet deployment is
+/// used by default.
+#[derive(Args, Default)]
+struct DeploymentArgs {
+    /// Package that publishes the `gamecards` module
+    #[arg(long)]
+    package: Option<ObjectID>,
+    /// Shared game room passed to `create_room`
+    #[arg(long, visible_alias = "room-id")]
+    room: Option<ObjectID>,
+    /// Version at which the room was shared; with it the room is never fetched, so it must
+    /// come with the room's ID
+    #[arg(long, requires = "room", value_parser = clap::value_parser!(u64).range(1..))]
+    room_initial_version: Option<u64>,
+    /// Whether the room is passed mutably [default: true]
+    #[arg(long, requires = "room_initial_version")]
+    room_mutable: Option<bool>,
+    /// Exact Move type of game cards [default: any type of the `gamecards` module]
+    #[arg(long, value_parser = parse_sui_struct_tag)]
+    card_type: Option<StructTag>,
+    /// Exact Move type of the rooms `create_room` returns, which go to `--room-owner`
+    /// [default: the type of the room `create_room` takes]
+    #[arg(long, value_parser = parse_sui_struct_tag)]
+    room_type: Option<StructTag>,
+}
+
+/// Where to write the IDs of the objects a command creates, for the next command to read.
+#[derive(Args, Default)]
+struct CreatedIdsArgs {
+    /// Write the IDs of the created objects to this file after execution
+    #[arg(long)]
+    created_ids_out: Option<PathBuf>,
+    /// Format of the created IDs file
+    #[arg(long, value_enum, default_value_t = IdsFormat::Lines, requires = "created_ids_out")]
+    created_ids_format: IdsFormat,
+    /// Only write the IDs of created objects of this Move type
+    #[arg(long, value_parser = parse_sui_struct_tag, requires = "created_ids_out")]
+    created_type: Option<StructTag>,
+}
+
+impl CreatedIdsArgs {
+    fn write<'a>(
+        &self,
+        responses: impl IntoIterator<Item = &'a SuiTransactionBlockResponse>,
+    ) -> Result<(), anyhow::Error> {
+        let Some(path) = &self.created_ids_out else {
+            return Ok(());
+        };
+        let ids: Vec<_> = responses
+            .into_iter()
+            .flat_map(|response| created::created_ids(response, self.created_type.as_ref()))
+            .collect();
+        created::write_ids(path, &ids, self.created_ids_format)?;
+        println!(
+            "Wrote {} created object IDs to {}",
+            ids.len(),
+            path.display()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Args, Default)]
+struct CreateRoomArgs {
+    #[command(flatten)]
+    deployment: DeploymentArgs,
+    #[command(flatten)]
+    created_ids: CreatedIdsArgs,
+    /// Game card owned by the sender
+    #[arg(long)]
+    card: Option<ObjectID>,
+    /// Extra object owned by the sender, passed to `create_room` after the cards; repeat
+    /// for several objects, which are passed in order
+    #[arg(long = "extra-object")]
+    extra_objects: Vec<ObjectID>,
+    /// Transfer the created room to this address, if `create_room` returns an owned object
+    /// of the room type; ignored with a warning otherwise
+    #[arg(long)]
+    room_owner: Option<SuiAddress>,
+    /// Print the resolved transaction plan and exit without signing or submitting it
+    #[arg(long)]
+    plan: bool,
+}
+
+#[derive(Args)]
+struct BatchCreateRoomArgs {
+    #[command(flatten)]
+    deployment: DeploymentArgs,
+    #[command(flatten)]
+    created_ids: CreatedIdsArgs,
+    /// File with one game card ID per line
+    #[arg(long)]
+    cards_file: PathBuf,
+    /// Dry-run the batch and report the projected total gas without executing anything
+    #[arg(long)]
+    estimate_only: bool,
+    /// Only dry-run this many operations and extrapolate the total from their average
+    #[arg(long, requires = "estimate_only")]
+    estimate_sample: Option<usize>,
+}
+
+#[derive(Args)]
+struct ShowObjectArgs {
+    /// Object to print
+    #[arg(long)]
+    id: ObjectID,
+    /// Read the object as it was at this version instead of its latest version
+    #[arg(long)]
+    at_version: Option<u64>,
+    /// Only fetch the object's Move fields, leaving out its BCS bytes, display and owner
+    #[arg(long)]
+    content_only: bool,
+}
+
+#[derive(Args)]
+struct DistributeCardsArgs {
+    /// Comma-separated `<card>:<recipient>` pairs
+    #[arg(long, value_delimiter = ',', required = true)]
+    assignments: Vec<CardAssignment>,
+}
+
+/// Address to query; defaults to the active address of the local wallet.
+#[derive(Args)]
+struct AddressArgs {
+    #[arg(long)]
+    address: Option<SuiAddress>,
+}
+
+#[derive(Args)]
+struct ListCardsArgs {
+    #[command(flatten)]
+    deployment: DeploymentArgs,
+    #[command(flatten)]
+    address: AddressArgs,
+}
+
+#[derive(Args)]
+struct TxArgs {
+    /// Digest of the transaction
+    #[arg(long)]
+    digest: TransactionDigest,
+    /// Print the inputs and commands of the programmable transaction instead of the
+    /// whole response
+    #[arg(long)]
+    show_ptb: bool,
+}
+
+#[derive(Args)]
+struct HistoryArgs {
+    #[command(flatten)]
+    address: AddressArgs,
+    /// Number of transactions to list
+    #[arg(long, default_value_t = 10)]
+    limit: usize,
+}
+
+#[derive(Args)]
+struct GasReportArgs {
+    #[command(flatten)]
+    address: AddressArgs,
+    /// Number of recent transactions to include
+    #[arg(long, default_value_t = 50)]
+    last: usize,
+}
+
+#[derive(Args)]
+struct PortfolioArgs {
+    #[command(flatten)]
+    address: AddressArgs,
+    /// Add approximate USD values from the price source
+    #[arg(long, requires = "price_source")]
+    usd: bool,
+    /// Price URL template; `{coin_type}` is replaced by the coin type and the response
+    /// must be a JSON number or an object with a `usd` or `price` field
+    #[arg(long)]
+    price_source: Option<String>,
+}
+
+#[derive(Args)]
+struct NewAccountArgs {
+    /// Signature scheme of the new key
+    #[arg(long, value_enum, default_value_t = KeyScheme::Ed25519)]
+    scheme: KeyScheme,
+    /// Alias of the new key in the keystore
+    #[arg(long)]
+    alias: Option<String>,
+    /// Request SUI from the faucet (devnet, testnet and localnet only) and wait for it
+    #[arg(long)]
+    fund: bool,
+}
+
+#[derive(Args)]
+struct TransferArgs {
+    /// A 0x address or a SuiNS name such as `alice.sui`
+    #[arg(long)]
+    recipient: Recipient,
+    /// Amount to send, in MIST
+    #[arg(long)]
+    amount: u64,
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    /// Address whose objects are exported [default: the active address]
+    #[arg(long)]
+    owner: Option<SuiAddress>,
+    /// JSON file to write
+    #[arg(long)]
+    out: PathBuf,
+    /// Only export objects of this Move type, e.g. `0x2::coin::Coin<0x2::sui::SUI>`
+    #[arg(long = "type", value_parser = parse_sui_struct_tag)]
+    object_type: Option<StructTag>,
+}
+
+#[derive(Args)]
+struct WatchPackageArgs {
+    #[command(flatten)]
+    deployment: DeploymentArgs,
+    /// How event fields are printed
+    #[arg(long, value_enum, default_value_t = EventFormat::Decoded)]
+    format: EventFormat,
+}
+
+#[derive(Args)]
+struct QuickstartArgs {
+    #[command(flatten)]
+    deployment: DeploymentArgs,
+    /// Game card owned by the sender
+    #[arg(long)]
+    card: Option<ObjectID>,
+    /// Join the new room by calling this `gamecards` function with it, such as `join_room`
+    #[arg(long, value_name = "FUNCTION")]
+    join: Option<String>,
+}
+
+#[derive(Args)]
+struct SweepArgs {
+    #[command(flatten)]
+    deployment: DeploymentArgs,
+    /// Game card owned by the sender
+    #[arg(long)]
+    card: Option<ObjectID>,
+    /// Values passed as the integer argument after the room and the cards, written as
+    /// `<name>:<start>..<end>` (end excluded) or `<name>:<start>..=<end>`
+    #[arg(long)]
+    arg_range: ArgRange,
+    /// Largest number of values to dry-run
+    #[arg(long, default_value_t = sweep::MAX_SWEEP_VALUES)]
+    max_values: u128,
+}
+
+#[derive(Args)]
+struct LoadArgs {
+    #[command(flatten)]
+    deployment: DeploymentArgs,
+    /// File with one game card ID per line; each transaction uses the next card
+    #[arg(long)]
+    cards_file: PathBuf,
+    /// Target number of `create_room` transactions per second
+    #[arg(long)]
+    rate: f64,
+    /// How long to keep submitting transactions, in seconds
+    #[arg(long)]
+    duration: u64,
+    /// SUI coins paying for gas, one transaction in flight per coin [default: the sender's
+    /// largest coins covering the gas budget, up to --max-in-flight]
+    #[arg(long, value_delimiter = ',')]
+    gas_coins: Vec<ObjectID>,
+    /// Number of coins picked when --gas-coins is not given
+    #[arg(long, default_value_t = 8)]
+    max_in_flight: usize,
+}
+
+#[derive(Args)]
+struct GasPreviewArgs {
+    #[command(flatten)]
+    address: AddressArgs,
+    /// Amount the coins must cover, in MIST [default: the gas budget]
+    #[arg(long)]
+    budget: Option<u64>,
+}
+
+#[derive(Args)]
+struct ExistsArgs {
+    /// File with one object ID per line, or `-` to read them from stdin
+    #[arg(long)]
+    ids_file: PathBuf,
+    /// Print the results as JSON instead of a table
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct EncodeArgArgs {
+    /// Move type of the argument, e.g. `u64`, `vector<address>` or `0x1::string::String`
+    #[arg(long = "type", value_parser = parse_sui_type_tag)]
+    type_tag: TypeTag,
+    /// The value as JSON; a bare word that is not valid JSON is taken as a string
+    #[arg(long)]
+    value: String,
+}
+
+#[derive(Args)]
+struct PingRpcArgs {
+    /// Comma-separated JSON-RPC URLs [default: the public full node of the network]
+    #[arg(long, value_delimiter = ',')]
+    urls: Vec<String>,
+    /// Number of requests sent to each endpoint
+    #[arg(long, default_value_t = 5)]
+    samples: usize,
+    /// Seconds before a request counts as failed
+    #[arg(long, default_value_t = 10)]
+    timeout_secs: u64,
+}
+
+#[derive(Args)]
+struct CompareGasArgs {
+    #[command(flatten)]
+    deployment: DeploymentArgs,
+    /// Game card owned by the sender
+    #[arg(long)]
+    card: Option<ObjectID>,
+    /// The two constructions to compare
+    #[arg(long, value_enum)]
+    strategy: Strategy,
+    /// Number of SUI coins paying for gas with `--strategy gas-coins`
+    #[arg(long, default_value_t = 2)]
+    coins: usize,
+}
+
+#[derive(Args)]
+struct DynamicFieldsArgs {
+    /// Object whose dynamic fields are listed, e.g. a game room
+    #[arg(long)]
+    id: ObjectID,
+    /// Number of fields requested from the full node at a time
+    #[arg(long, default_value_t = 50)]
+    page_size: usize,
+}
+
+#[derive(Args)]
+struct DecodeManyArgs {
+    /// File with one object ID per line, or `-` to read them from stdin
+    #[arg(long)]
+    ids_file: PathBuf,
+    /// Move type of the objects, e.g. `0x...::gamecards::Card`
+    #[arg(long = "type", value_parser = parse_sui_struct_tag)]
+    object_type: StructTag,
+    /// Most objects decoded at the same time
+    #[arg(long, default_value = "8")]
+    concurrency: NonZeroUsize,
+}
+
+#[derive(Args)]
+struct ScriptArgs {
+    /// File with one command per line, without the binary name; `<name> = <command>`
+    /// binds the IDs of the objects the command creates to `$name`, `$name.1` and so on. Lines
+    /// take no global flags: every command runs with the ones given before `script`
+    #[arg(long)]
+    file: PathBuf,
+    /// Run the remaining lines after a line fails, and fail at the end instead
+    #[arg(long)]
+    continue_on_error: bool,
+}
+
+/// A line of a script, parsed like the command line without the binary name and without
+/// global flags, which are those of the `script` command itself.
+#[derive(Parser)]
+#[command(no_binary_name = true)]
+struct ScriptCommand {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Args)]
+struct ValidateConfigArgs {
+    /// Also check that the package, the room and the card type's package exist on the
+    /// config file's network
+    #[arg(long)]
+    online: bool,
+}
+
+#[derive(Args)]
+struct ShowCreateRoomArgs {
+    #[command(flatten)]
+    deployment: DeploymentArgs,
+    /// Game card owned by the sender
+    #[arg(long)]
+    card: Option<ObjectID>,
+    /// Extra object passed to `create_room` after the cards; repeat for several objects
+    #[arg(long = "extra-object")]
+    extra_objects: Vec<ObjectID>,
+    /// Do not connect to a full node: use placeholder versions and digests for the
+    /// objects, and leave out the transfer of objects returned by `create_room`
+    #[arg(long)]
+    offline: bool,
+}
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
-    // 1) Get the Sui client, the sender, and recipient addresses for the transaction
-    // and find the coin we will use as gas
-    let (sui, sender, recipient) = setup_for_write().await?;
-
-    // 2) Retrieve the coins for the sender address
-    let coins = sui
-        .coin_read_api()
-        .get_coins(sender, None, None, None)
-        .await?;
-    // Use the first coin from the list as the gas coin
-    let coin = coins.data.into_iter().next().unwrap();
-
-    // 3) Create a new programmable transaction builder
-    let mut ptb = ProgrammableTransactionBuilder::new();
-
-    // 4) Fetch game room information
-    // Define the game room object ID
-    let game_room_id = ObjectID::from_hex_literal("0x52509952e7b80b08880238e9737e8f70e223418816e5a85bf82575ef84ecc545")
-        .unwrap();
-    // Define the object ID
-    let object_id = ObjectID::from_hex_literal("0xb28e2aa6a21db55873a1b81983cbd19544459971b67ba2ddbd7b8d6575d7c2d1").unwrap();
-    // Fetch the game room object details with specified options
-    let object = sui.read_api().get_object_with_options(object_id,
-            SuiObjectDataOptions {
-                show_type: true,
-                show_owner: true,
-                show_previous_transaction: true,
-                show_display: true,
-                show_content: true,
-                show_bcs: true,
-                show_storage_rebate: true,
-            },
-        ).await?;
-
-    // Get the version of the game room object
-    let object_version = object.clone().data.unwrap().version;
-    // Specify if the object is mutable
-    let is_mutable = true;
-    // Create a CallArg for the game room object
-    let game_room_input = CallArg::Object(ObjectArg::SharedObject{
-        id: game_room_id,
-        initial_shared_version: object_version,
-        mutable: is_mutable, 
-    });
-    // Add the game room object as an input to the transaction
-    ptb.input(game_room_input);
-    
-    // 5) Fetch game card information
-    // Define the game card object ID
-    let game_card_id = ObjectID::from_hex_literal("0x440b328ba3c90f203f439f6fc4c5aa40b7ca41d28317d5bb9b6c0207cfebc693")
-        .unwrap();
-    // Fetch the game card object details with specified options
-    let game_card_object = sui.read_api().get_object_with_options(game_card_id,
-            SuiObjectDataOptions {
-                show_type: true,
-                show_owner: true,
-                show_previous_transaction: true,
-                show_display: true,
-                show_content: true,
-                show_bcs: true,
-                show_storage_rebate: true,
-            },
-        ).await?;
-
-    // Get the version of the game card object
-    let game_card_version = game_card_object.clone().data.unwrap().version;
-    // Get the digest of the game card object
-    let game_card_digests = game_card_object.data.unwrap().digest;
-    // Create an ObjectRef for the game card object
-    let game_card_object_ref: ObjectRef = (game_card_id, game_card_version, game_card_digests);
-    // Create a CallArg for the game card object
-    let game_card_input = CallArg::Object(ObjectArg::ImmOrOwnedObject(game_card_object_ref));
-    // Add the game card object as an input to the transaction
-    ptb.input(game_card_input);
-
-    // 6) Add commands to the programmable transaction builder
-    // Add a command to create a Move vector with one element
-    ptb.command(Command::MakeMoveVec(None, vec![
-        Argument::Input(1),
-    ]));
-
-    // Add a command to call the `create_room` function in the `gamecards` module
-    ptb.command(Command::MoveCall(Box::new(
-        sui_sdk::types::transaction::ProgrammableMoveCall {
-            package: ObjectID::from_hex_literal("0xc74620c25579b75ac8f6d0d670a4663944ff7f29d6e856f6b33e0a35a34c5a06").unwrap(),
-            module: Identifier::new("gamecards").unwrap(),
-            function: Identifier::new("create_room").unwrap(),
-            type_arguments: vec![],
-            arguments: vec![
-                Argument::Input(0),
-                Argument::Result(0),
-            ],
-        }
-    )));
-
-    // 7) Finish building the transaction block by calling finish on the programmable transaction builder
-    let builder = ptb.finish();
-
-    // Define the gas budget for the transaction
-    let gas_budget = 10_000_000;
-    // Get the current reference gas price
-    let gas_price = sui.read_api().get_reference_gas_price().await?;
-    // Create the transaction data that will be sent to the network
-    let tx_data = TransactionData::new_programmable(
-        sender,
-        vec![coin.object_ref()],
-        builder,
-        gas_budget,
-        gas_price,
-    );
-
-    // 8) Sign the transaction
-    // Load the keystore from the Sui config directory
-    let keystore = FileBasedKeystore::new(&sui_config_dir()?.join(SUI_KEYSTORE_FILENAME))?;
-    // Sign the transaction data using the sender's key
-    let signature = keystore.sign_secure(&sender, &tx_data, Intent::sui_transaction())?;
-
-    // 9) Execute the transaction
+    let mut cli = Cli::parse();
+    #[cfg(feature = "tracing-export")]
+    let _trace_guard = match &cli.global.trace_out {
+        Some(target) => Some(sui_connect::telemetry::init(target)?),
+        None => None,
+    };
+    // Loading the config below stops at its first problem, which is what this reports on
+    if let Some(Commands::ValidateConfig(args)) = &cli.command {
+        return validate_config(args, &cli.global).await;
+    }
+    cli.global.settings = Config::load_or_default(cli.global.config.as_deref())?;
+    if let Some(path) = &cli.global.transcript {
+        let transcript = Transcript::create(path)?;
+        transcript.decision(
+            "session",
+            serde_json::json!({
+                "version": env!("CARGO_PKG_VERSION"),
+                "network": format!("{:?}", cli.global.network()),
+                "rpc_url": transcript::redact_url(cli.global.rpc_url()),
+            }),
+        );
+        cli.global.transcript_file = Some(transcript);
+    }
+    let command = cli
+        .command
+        .unwrap_or_else(|| Commands::CreateRoom(CreateRoomArgs::default()));
+    // Every RPC of the command goes to the transcript, not only those of its game client
+    Transcript::scope(
+        cli.global.transcript_file.as_ref(),
+        run(command, &cli.global),
+    )
+    .await
+}
+
+async fn run(command: Commands, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    match command {
+        Commands::CreateRoom(args) => create_room(args, global).await,
+        Commands::BatchCreateRoom(args) => batch_create_room(args, global).await,
+        Commands::ShowObject(args) => show_object(args, global).await,
+        Commands::DistributeCards(args) => distribute_cards(args, global).await,
+        Commands::Status => status(global).await,
+        Commands::Balance(args) => balance(args, global).await,
+        Commands::ListCards(args) => list_cards(args, global).await,
+        Commands::Tx(args) => tx(args, global).await,
+        Commands::History(args) => history(args, global).await,
+        Commands::GasReport(args) => gas_report(args, global).await,
+        Commands::Portfolio(args) => portfolio(args, global).await,
+        Commands::NewAccount(args) => new_account(args, global).await,
+        Commands::InitWallet => init_wallet(),
+        Commands::Transfer(args) => transfer(args, global).await,
+        Commands::Export(args) => export(args, global).await,
+        Commands::WatchPackage(args) => watch_package(args, global).await,
+        Commands::Quickstart(args) => quickstart(args, global).await,
+        Commands::Sweep(args) => sweep(args, global).await,
+        Commands::Load(args) => load(args, global).await,
+        Commands::GasPreview(args) => gas_preview(args, global).await,
+        Commands::Exists(args) => exists(args, global).await,
+        Commands::EncodeArg(args) => encode_arg(args),
+        Commands::PingRpc(args) => ping_rpc(args, global).await,
+        Commands::CompareGas(args) => compare_gas(args, global).await,
+        Commands::DynamicFields(args) => dynamic_fields(args, global).await,
+        Commands::ValidateConfig(args) => validate_config(&args, global).await,
+        Commands::Script(args) => script(args, global).await,
+        Commands::DecodeMany(args) => decode_many(args, global).await,
+        Commands::ShowBuilder(ShowBuilderCommands::CreateRoom(args)) => {
+            show_create_room_builder(args, global).await
+        }
+    }
+}
+
+async fn create_room(args: CreateRoomArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    // 1) Get the Sui client and the sender address for the transaction
+    let (sui, sender) = global.setup_for_write().await?;
+    let client = global.game_client(sui, sender, global.deployment(&args.deployment));
+
+    // 2) Resolve the room, the card, the gas coin and the gas price into a plan
+    let card = args.card.unwrap_or_else(game::default_card);
+    let action = Action::CreateRoom {
+        card,
+        extra_objects: args.extra_objects,
+        room_owner: args.room_owner,
+    };
+    if args.plan {
+        println!("{}", client.plan(&action).await?);
+        return Ok(());
+    }
+
+    // 3) Sign and execute the transaction
     print!("Executing the transaction...");
-    // Execute the transaction block and wait for local execution
-    let transaction_response = sui
-        .quorum_driver_api()
-        .execute_transaction_block(
-            Transaction::from_data(tx_data, vec![signature]),
-            SuiTransactionBlockResponseOptions::full_content(),
-            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
-        )
-        .await?;
+    let transaction_response = client.execute(&action).await?;
     // Print the transaction response
     print!("done\nTransaction information: ");
     println!("{:?}", transaction_response);
+    args.created_ids.write([&transaction_response])?;
+    global.check_assertions(&transaction_response)
+}
+
+async fn batch_create_room(
+    args: BatchCreateRoomArgs,
+    global: &GlobalArgs,
+) -> Result<(), anyhow::Error> {
+    let (sui, sender) = global.setup_for_write().await?;
+    let client = global.game_client(sui, sender, global.deployment(&args.deployment));
+    let cards = batch::read_card_ids(&args.cards_file)?;
+
+    if args.estimate_only {
+        let estimate = batch::estimate_create_rooms(&client, &cards, args.estimate_sample).await?;
+        print_batch_estimate(&estimate);
+        Ok(())
+    } else {
+        // Rooms created before a failure still have their IDs written
+        let (responses, result) = match batch::create_rooms(&client, &cards).await {
+            Ok(responses) => (responses, Ok(())),
+            Err(failed) => (failed.responses, Err(failed.error)),
+        };
+        for (i, (card, response)) in cards.iter().zip(&responses).enumerate() {
+            let status = response
+                .effects
+                .as_ref()
+                .map(|effects| format!("{:?}", effects.status()))
+                .unwrap_or_else(|| "unknown".to_string());
+            println!("[{}] card {card}: {} ({status})", i + 1, response.digest);
+        }
+        args.created_ids.write(&responses)?;
+        result?;
+        responses
+            .iter()
+            .try_for_each(|response| global.check_assertions(response))
+    }
+}
+
+fn print_batch_estimate(estimate: &batch::BatchEstimate) {
+    if estimate.operations == 0 {
+        println!("The batch is empty, nothing to estimate");
+        return;
+    }
+    for dry_run in &estimate.dry_runs {
+        let cost = format_sui(dry_run.net_cost);
+        match &dry_run.error {
+            None => println!("[{}] card {}: {cost}", dry_run.index + 1, dry_run.card),
+            Some(error) => println!(
+                "[{}] card {}: {cost} (would fail: {error})",
+                dry_run.index + 1,
+                dry_run.card
+            ),
+        }
+    }
+
+    let dry_runs = estimate.dry_runs.len();
+    if let (true, Some(average)) = (estimate.is_sampled(), estimate.average_net_cost()) {
+        println!(
+            "Average gas over {dry_runs} sampled operations: {}",
+            format_sui(average)
+        );
+    }
+    println!(
+        "Projected total gas for {} operations: {}",
+        estimate.operations,
+        format_sui(estimate.projected_total())
+    );
+    let failures = estimate.failures();
+    if failures > 0 {
+        println!("{failures} of the dry-run operations would fail");
+    }
+}
+
+async fn show_object(args: ShowObjectArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let sui = global.setup_for_read().await?;
+    let options = if args.content_only {
+        game::content_object_options()
+    } else {
+        game::full_object_options()
+    };
+    let object = match args.at_version {
+        Some(version) => {
+            game::get_past_object(&sui, args.id, SequenceNumber::from_u64(version), options).await?
+        }
+        None => game::get_object_with_options(&sui, args.id, options).await?,
+    };
+    println!("{}", serde_json::to_string_pretty(&object)?);
+    Ok(())
+}
+
+async fn distribute_cards(
+    args: DistributeCardsArgs,
+    global: &GlobalArgs,
+) -> Result<(), anyhow::Error> {
+    let (sui, sender) = global.setup_for_write().await?;
+    let client = global.game_client(sui, sender, global.settings.deployment());
+    let cards: Vec<_> = args.assignments.iter().map(|a| a.card).collect();
+
+    let response = client
+        .execute(&Action::DistributeCards {
+            assignments: args.assignments,
+        })
+        .await?;
+    println!("Transaction digest: {}", response.digest);
+
+    // Report where each card ended up according to the transaction effects
+    let effects = response
+        .effects
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("The response does not contain transaction effects"))?;
+    println!("Status: {:?}", effects.status());
+    for card in cards {
+        let owner = effects
+            .mutated()
+            .iter()
+            .find(|object| object.reference.object_id == card)
+            .map(|object| format!("{:?}", object.owner))
+            .unwrap_or_else(|| "not found in effects".to_string());
+        println!("{card}: {owner}");
+    }
+    global.check_assertions(&response)
+}
+
+async fn status(global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let sui = global.setup_for_read().await?;
+    println!("Chain identifier: {}", game::chain_identifier(&sui).await?);
+    println!(
+        "Latest checkpoint: {}",
+        game::latest_checkpoint(&sui).await?
+    );
+    println!(
+        "Reference gas price: {} MIST",
+        gas::gas_price(&sui, 0).await?
+    );
+    Ok(())
+}
+
+async fn balance(args: AddressArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let address = address_or_active(args.address)?;
+    let client =
+        global.read_only_client(global.setup_for_read().await?, global.settings.deployment());
+    let balance = client.balance(address).await?;
+    println!(
+        "{address}: {} in {} coins",
+        format_sui(i128::try_from(balance.total_balance)?),
+        balance.coin_object_count
+    );
+    Ok(())
+}
+
+async fn list_cards(args: ListCardsArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let owner = address_or_active(args.address.address)?;
+    let client = global.read_only_client(
+        global.setup_for_read().await?,
+        global.deployment(&args.deployment),
+    );
+    for card in client.cards(owner).await? {
+        let object_type = match &card.content {
+            Some(SuiParsedData::MoveObject(object)) => object.type_.to_string(),
+            _ => String::new(),
+        };
+        println!("{} {object_type}", card.object_id);
+    }
+    Ok(())
+}
+
+async fn tx(args: TxArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let client =
+        global.read_only_client(global.setup_for_read().await?, global.settings.deployment());
+    if args.show_ptb {
+        match client.transaction_kind(args.digest).await? {
+            TransactionKind::ProgrammableTransaction(pt) => print_ptb(&pt),
+            kind => println!(
+                "{} is a {} transaction, not a programmable transaction",
+                args.digest,
+                kind.name()
+            ),
+        }
+        return Ok(());
+    }
+    let transaction = client.transaction(args.digest).await?;
+    println!("{:?}", transaction);
     Ok(())
 }
+
+async fn history(args: HistoryArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let address = address_or_active(args.address.address)?;
+    let client =
+        global.read_only_client(global.setup_for_read().await?, global.settings.deployment());
+    for transaction in client.history(address, args.limit).await? {
+        let status = transaction
+            .effects
+            .as_ref()
+            .map(|effects| format!("{:?}", effects.status()))
+            .unwrap_or_else(|| "unknown".to_string());
+        println!("{} {status}", transaction.digest);
+    }
+    Ok(())
+}
+
+async fn gas_report(args: GasReportArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let address = address_or_active(args.address.address)?;
+    let client =
+        global.read_only_client(global.setup_for_read().await?, global.settings.deployment());
+    let transactions = client.history(address, args.last).await?;
+    let report = GasReport::from_transactions(&transactions);
+
+    println!(
+        "Gas spent by the last {} transactions of {address}:",
+        transactions.len()
+    );
+    println!(
+        "  Computation:    {}",
+        format_sui(report.computation_cost as i128)
+    );
+    println!(
+        "  Storage:        {}",
+        format_sui(report.storage_cost as i128)
+    );
+    println!(
+        "  Storage rebate: {}",
+        format_sui(report.storage_rebate as i128)
+    );
+    println!("  Net total:      {}", format_sui(report.net_cost()));
+    if let Some(average) = report.average_net_cost() {
+        println!("  Net average:    {} per transaction", format_sui(average));
+    }
+    if report.missing_effects > 0 {
+        println!(
+            "  {} transactions were returned without effects and are not counted",
+            report.missing_effects
+        );
+    }
+    Ok(())
+}
+
+async fn portfolio(args: PortfolioArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let address = address_or_active(args.address.address)?;
+    let sui = global.setup_for_read().await?;
+    let holdings = portfolio::holdings(&sui, address).await?;
+
+    let values = match (&args.price_source, args.usd) {
+        (Some(url), true) => portfolio::usd_values(&holdings, &HttpPriceSource::new(url)).await?,
+        _ => vec![None; holdings.len()],
+    };
+
+    println!("Holdings of {address}:");
+    for (holding, value) in holdings.iter().zip(&values) {
+        let amount = match holding.amount() {
+            Some(amount) => format!("{amount}"),
+            None => format!("{} (raw units)", holding.balance),
+        };
+        let symbol = holding.symbol.as_deref().unwrap_or("?");
+        match value {
+            Some(value) => println!(
+                "  {amount} {symbol} ~${value:.2} (estimate)  {}",
+                holding.coin_type
+            ),
+            None => println!("  {amount} {symbol}  {}", holding.coin_type),
+        }
+    }
+    if args.usd {
+        let priced = values.iter().flatten().count();
+        let total: f64 = values.iter().flatten().sum();
+        println!(
+            "Estimated total: ~${total:.2} ({priced} of {} coin types priced)",
+            holdings.len()
+        );
+    }
+    Ok(())
+}
+
+async fn new_account(args: NewAccountArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    if args.fund && global.network().faucet_url().is_none() {
+        anyhow::bail!("There is no faucet on {}", global.network());
+    }
+
+    let (address, phrase) = utils::generate_account(args.scheme.signature_scheme(), args.alias)?;
+    println!("Created account {address}");
+    println!("Recovery phrase (keep it secret): {phrase}");
+
+    if args.fund {
+        let sui = global.setup_for_read().await?;
+        print!("Requesting SUI from the {} faucet...", global.network());
+        utils::request_tokens_from_faucet(&sui, global.network(), address).await?;
+        let balance = utils::balance(&sui, address).await?;
+        println!(
+            "done\nBalance: {}",
+            format_sui(i128::try_from(balance.total_balance)?)
+        );
+    }
+    Ok(())
+}
+
+fn init_wallet() -> Result<(), anyhow::Error> {
+    let mut wallet = utils::retrieve_wallet()?;
+    println!("Active address: {}", wallet.active_address()?);
+    for address in wallet.get_addresses() {
+        println!("  {address}");
+    }
+    Ok(())
+}
+
+async fn transfer(args: TransferArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let (sui, sender) = global.setup_for_write().await?;
+    let resolver = NameResolver::new(global.rpc_url());
+    let recipient = args.recipient.resolve(&resolver).await?;
+    if let Recipient::Name(name) = &args.recipient {
+        println!("{name} resolves to {recipient}");
+    }
+
+    let client = global.game_client(sui, sender, global.settings.deployment());
+    let response = client
+        .execute(&Action::TransferSui {
+            recipient,
+            amount: args.amount,
+        })
+        .await?;
+    let status = response
+        .effects
+        .as_ref()
+        .map(|effects| format!("{:?}", effects.status()))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!(
+        "Sent {} to {recipient}: {} ({status})",
+        format_sui(args.amount),
+        response.digest
+    );
+    global.check_assertions(&response)
+}
+
+async fn export(args: ExportArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let owner = address_or_active(args.owner)?;
+    let client =
+        global.read_only_client(global.setup_for_read().await?, global.settings.deployment());
+    let filter = args.object_type.map(SuiObjectDataFilter::StructType);
+    let objects = client
+        .owned_objects(owner, filter, game::full_object_options())
+        .await?;
+
+    let file = std::fs::File::create(&args.out)?;
+    serde_json::to_writer_pretty(std::io::BufWriter::new(file), &objects)?;
+    println!(
+        "Exported {} objects owned by {owner} to {}",
+        objects.len(),
+        args.out.display()
+    );
+    Ok(())
+}
+
+async fn watch_package(args: WatchPackageArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let package = global.deployment(&args.deployment).package;
+    let decoder = EventDecoder::new(global.setup_for_read().await?, args.format);
+    println!("Watching events of package {package}...");
+    events::watch_events(
+        global.rpc_url(),
+        EventFilter::Package(package),
+        &global.settings.rpc.subscription,
+        |event| {
+            let decoder = &decoder;
+            async move {
+                let fields = decoder.fields(&event).await;
+                println!("{}\n", events::describe_event(&event, &fields));
+            }
+        },
+    )
+    .await
+}
+
+async fn quickstart(args: QuickstartArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    // The balance checked and funded below is the sender's SUI
+    if let Some(coin_type) = gas::non_sui_gas_type(global.gas_coin_type.clone()) {
+        anyhow::bail!("quickstart pays for gas in SUI and cannot use --gas-coin-type {coin_type}");
+    }
+    let network = global.network();
+
+    println!(
+        "1) Connecting to the {network} full node at {}",
+        transcript::redact_url(global.rpc_url())
+    );
+    println!("   Every other step talks to this node, so it has to be reachable first.");
+    let (sui, sender) = global.setup_for_write().await?;
+    println!(
+        "   Connected, chain identifier {}",
+        game::chain_identifier(&sui).await?
+    );
+
+    println!("2) Checking the balance of the sender {sender}");
+    println!("   The sender pays for gas, so it needs at least the gas budget in SUI.");
+    let client = global.game_client(sui, sender, global.deployment(&args.deployment));
+    let mut balance = client.balance(sender).await?.total_balance;
+    if balance < u128::from(global.gas_budget) {
+        if network.faucet_url().is_none() {
+            anyhow::bail!(
+                "{sender} only has {} but the gas budget is {}, and there is no faucet on {network}",
+                format_sui(i128::try_from(balance)?),
+                format_sui(global.gas_budget)
+            );
+        }
+        println!(
+            "   Only {}, requesting SUI from the {network} faucet...",
+            format_sui(i128::try_from(balance)?)
+        );
+        utils::request_tokens_from_faucet(client.sui(), network, sender).await?;
+        balance = client.balance(sender).await?.total_balance;
+    }
+    println!("   Balance: {}", format_sui(i128::try_from(balance)?));
+
+    let card = args.card.unwrap_or_else(game::default_card);
+    println!("3) Creating a room from card {card}");
+    println!(
+        "   This calls gamecards::create_room with the shared game room {} and the card.",
+        client.deployment().room
+    );
+    let response = client
+        .execute(&Action::CreateRoom {
+            card,
+            extra_objects: vec![],
+            room_owner: None,
+        })
+        .await?;
+    println!("   Transaction digest: {}", response.digest);
+    if let Some(effects) = &response.effects {
+        if let SuiExecutionStatus::Failure { error } = effects.status() {
+            anyhow::bail!("create_room failed: {error}");
+        }
+    }
+
+    println!("4) Looking up the new room in the transaction's object changes");
+    let room = response
+        .object_changes
+        .unwrap_or_default()
+        .into_iter()
+        .find_map(|change| match change {
+            ObjectChange::Created {
+                object_id, owner, ..
+            } if matches!(owner, Owner::Shared { .. }) => Some(object_id),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow::anyhow!("The transaction did not create a shared room"))?;
+    println!("   Room ID: {room}");
+    if let Some(url) = network.explorer_object_url(room) {
+        println!("   Explorer: {url}");
+    }
+
+    if let Some(function) = args.join {
+        println!("5) Joining the room with gamecards::{function}");
+        println!("   The room is now shared, so any address can call into it, the sender too.");
+        let response = client.execute(&Action::JoinRoom { room, function }).await?;
+        println!("   Transaction digest: {}", response.digest);
+        if let Some(effects) = &response.effects {
+            if let SuiExecutionStatus::Failure { error } = effects.status() {
+                anyhow::bail!("Joining the room failed: {error}");
+            }
+        }
+    }
+    println!("Your setup works; see --help for the other subcommands.");
+    Ok(())
+}
+
+async fn sweep(args: SweepArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let (sui, sender) = global.setup_for_write().await?;
+    let client = global.game_client(sui, sender, global.deployment(&args.deployment));
+    let card = args.card.unwrap_or_else(game::default_card);
+
+    let results = sweep::sweep_create_room(&client, card, &args.arg_range, args.max_values).await?;
+    println!("{:>20}  {:>20}  result", args.arg_range.name, "gas");
+    for result in results {
+        println!(
+            "{:>20}  {:>20}  {}",
+            result.value,
+            format_sui(result.gas),
+            result.error.as_deref().unwrap_or("success")
+        );
+    }
+    Ok(())
+}
+
+async fn load(args: LoadArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let (sui, sender) = global.setup_for_write().await?;
+    let deployment = global.deployment(&args.deployment);
+    let cards = batch::read_card_ids(&args.cards_file)?;
+    let duration = Duration::from_secs(args.duration);
+
+    let coin_type = gas::non_sui_gas_type(global.gas_coin_type.clone());
+    let coin_ids = if args.gas_coins.is_empty() {
+        let mut coins = gas::coins_of_type(&sui, sender, coin_type.as_ref()).await?;
+        coins.retain(|coin| coin.balance >= global.gas_budget);
+        coins.sort_by_key(|coin| std::cmp::Reverse(coin.balance));
+        coins.truncate(args.max_in_flight);
+        coins.into_iter().map(|coin| coin.coin_object_id).collect()
+    } else {
+        args.gas_coins
+    };
+    if coin_ids.is_empty() {
+        anyhow::bail!(
+            "{sender} has no coin holding the gas budget of {}",
+            gas::format_gas(global.gas_budget, coin_type.as_ref())
+        );
+    }
+
+    // Each client pays from a pool of its own coin, so that no two transactions in flight
+    // lock the same gas coin
+    let mut clients = Vec::with_capacity(coin_ids.len());
+    for coin in coin_ids {
+        let pool = GasPool::new(&sui, sender, vec![coin], coin_type.clone()).await?;
+        clients.push(
+            global
+                .game_client(sui.clone(), sender, deployment.clone())
+                .with_gas_pool(pool),
+        );
+    }
+
+    let planned = (args.rate * duration.as_secs_f64()).ceil() as usize;
+    if planned > cards.len() {
+        println!(
+            "Only {} cards for the {planned} transactions planned; the test ends when they run out",
+            cards.len()
+        );
+    }
+    println!(
+        "Creating rooms at {} per second for {}s with {} gas coins",
+        args.rate,
+        args.duration,
+        clients.len()
+    );
+    let report = load::create_rooms_at_rate(&clients, &cards, args.rate, duration).await?;
+
+    println!(
+        "Submitted {} transactions in {:.1}s",
+        report.submitted,
+        report.elapsed.as_secs_f64()
+    );
+    println!(
+        "Succeeded: {} ({:.1}%)",
+        report.succeeded,
+        report.success_rate() * 100.0
+    );
+    println!(
+        "Achieved: {:.2} TPS of {} requested",
+        report.achieved_tps(),
+        args.rate
+    );
+    for pct in [50.0, 90.0, 99.0, 100.0] {
+        if let Some(latency) = report.percentile(pct) {
+            println!("Latency p{pct}: {} ms", latency.as_millis());
+        }
+    }
+    Ok(())
+}
+
+async fn gas_preview(args: GasPreviewArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let owner = address_or_active(args.address.address)?;
+    let sui = global.setup_for_read().await?;
+    let budget = args.budget.unwrap_or(global.gas_budget);
+
+    let coin_type = gas::non_sui_gas_type(global.gas_coin_type.clone());
+    let coin_type = coin_type.as_ref();
+    let selection = gas::select_gas_coins(&sui, owner, budget, coin_type).await?;
+    println!(
+        "Coins of {owner} selected for {}:",
+        gas::format_gas(budget, coin_type)
+    );
+    for coin in &selection.coins {
+        println!(
+            "  {} (version {}): {}",
+            coin.coin_object_id,
+            coin.version.value(),
+            gas::format_gas(coin.balance, coin_type)
+        );
+    }
+    if selection.needs_merge() {
+        println!(
+            "No single coin is large enough; these {} coins ({} in total) must be merged first",
+            selection.coins.len(),
+            gas::format_gas(selection.total(), coin_type)
+        );
+    } else {
+        println!("A single coin covers the budget, no merge is needed");
+    }
+    Ok(())
+}
+
+/// Read object IDs, one per line, from a file or from stdin if the path is `-`.
+fn read_ids_file(path: &Path) -> Result<Vec<ObjectID>, anyhow::Error> {
+    let contents = if path.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin()).context("Cannot read the standard input")?
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?
+    };
+    batch::parse_object_ids(&contents)
+}
+
+async fn exists(args: ExistsArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let ids = read_ids_file(&args.ids_file)?;
+    let sui = global.setup_for_read().await?;
+    let statuses = game::object_statuses(&sui, &ids).await?;
+
+    if args.json {
+        let rows: Vec<_> = ids
+            .iter()
+            .zip(&statuses)
+            .map(|(id, status)| {
+                let mut row = serde_json::to_value(status)?;
+                row["id"] = serde_json::json!(id);
+                Ok(row)
+            })
+            .collect::<Result<_, serde_json::Error>>()?;
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    } else {
+        for (id, status) in ids.iter().zip(&statuses) {
+            println!("{id} {status}");
+        }
+    }
+    Ok(())
+}
+
+fn encode_arg(args: EncodeArgArgs) -> Result<(), anyhow::Error> {
+    let value = serde_json::from_str(&args.value)
+        .unwrap_or_else(|_| serde_json::Value::String(args.value.clone()));
+    let bytes = pure::encode(&args.type_tag, &value)?;
+    println!("Hex: 0x{}", hex::encode(&bytes));
+    println!(
+        "Base64: {}",
+        base64::engine::general_purpose::STANDARD.encode(&bytes)
+    );
+    Ok(())
+}
+
+async fn ping_rpc(args: PingRpcArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let urls = if args.urls.is_empty() {
+        vec![global.rpc_url().to_string()]
+    } else {
+        args.urls
+    };
+    let timeout = Duration::from_secs(args.timeout_secs);
+
+    let mut stats = Vec::with_capacity(urls.len());
+    for url in &urls {
+        stats.push(ping::ping(url, args.samples, timeout).await);
+    }
+    ping::rank(&mut stats);
+
+    let ms = |latency: Option<Duration>| {
+        latency
+            .map(|latency| format!("{:.1}", latency.as_secs_f64() * 1000.0))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    println!(
+        "{:>8} {:>8} {:>8} {:>8}  url",
+        "min ms", "avg ms", "max ms", "success"
+    );
+    for endpoint in &stats {
+        println!(
+            "{:>8} {:>8} {:>8} {:>7.0}%  {}",
+            ms(endpoint.min()),
+            ms(endpoint.avg()),
+            ms(endpoint.max()),
+            endpoint.success_rate() * 100.0,
+            endpoint.url
+        );
+        if let Some(error) = &endpoint.error {
+            println!("{:>37}last error: {error}", "");
+        }
+    }
+    Ok(())
+}
+
+async fn show_create_room_builder(
+    args: ShowCreateRoomArgs,
+    global: &GlobalArgs,
+) -> Result<(), anyhow::Error> {
+    let deployment = global.deployment(&args.deployment);
+    let card = args.card.unwrap_or_else(game::default_card);
+
+    let ptb = if args.offline {
+        // Versions and digests only matter to validators, so any value shows the structure
+        let placeholder =
+            |id| ObjectArg::ImmOrOwnedObject((id, SequenceNumber::new(), ObjectDigest::MIN));
+        let room_ref = deployment.room_ref.unwrap_or(game::RoomRef {
+            initial_shared_version: SequenceNumber::new(),
+            mutable: true,
+        });
+        let room = ObjectArg::SharedObject {
+            id: deployment.room,
+            initial_shared_version: room_ref.initial_shared_version,
+            mutable: room_ref.mutable,
+        };
+        let extra_objects: Vec<_> = args
+            .extra_objects
+            .iter()
+            .map(|&id| placeholder(id))
+            .collect();
+        println!("Offline: object versions and digests are placeholders, and objects returned by create_room are not transferred");
+        game::build_create_room(&deployment, room, placeholder(card), &extra_objects, &[])?
+    } else {
+        let (sui, sender) = global.setup_for_write().await?;
+        let request = game::CreateRoom {
+            card,
+            extra_objects: &args.extra_objects,
+            room_owner: None,
+            check_type: !global.no_type_check,
+        };
+        game::create_room_builder(
+            &sui,
+            &ModuleCache::new(sui.clone()),
+            &deployment,
+            sender,
+            request,
+        )
+        .await?
+    };
+
+    println!("Steps:");
+    for (i, step) in ptb.steps().iter().enumerate() {
+        println!("  {}. {step}", i + 1);
+    }
+    print_ptb(&ptb.finish());
+    Ok(())
+}
+
+async fn compare_gas(args: CompareGasArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let (sui, sender) = global.setup_for_write().await?;
+    let client = global.game_client(sui, sender, global.deployment(&args.deployment));
+    let card = args.card.unwrap_or_else(game::default_card);
+
+    let variants = compare::compare_create_room(&client, card, args.strategy, args.coins).await?;
+    println!(
+        "{:<28} {:>16} {:>16} {:>16} {:>16}  result",
+        "strategy", "computation", "storage", "rebate", "net"
+    );
+    for variant in &variants {
+        let summary = &variant.estimate.summary;
+        println!(
+            "{:<28} {:>16} {:>16} {:>16} {:>16}  {}",
+            variant.name,
+            format_sui(summary.computation_cost),
+            format_sui(summary.storage_cost),
+            format_sui(summary.storage_rebate),
+            format_sui(variant.estimate.net_cost()),
+            variant.estimate.error.as_deref().unwrap_or("success")
+        );
+    }
+    let [first, second] = &variants;
+    let difference = second.estimate.net_cost() - first.estimate.net_cost();
+    if difference == 0 {
+        println!("{} and {} have an equal cost", second.name, first.name);
+        return Ok(());
+    }
+    println!(
+        "{} costs {} {} than {}",
+        second.name,
+        format_sui(difference.abs()),
+        if difference > 0 { "more" } else { "less" },
+        first.name
+    );
+    Ok(())
+}
+
+async fn dynamic_fields(args: DynamicFieldsArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let sui = global.setup_for_read().await?;
+    let fields = dynamic_fields::dynamic_fields(&sui, args.id, args.page_size).await?;
+    if fields.is_empty() {
+        println!("{} has no dynamic fields", args.id);
+        return Ok(());
+    }
+    let decoder = MoveValueDecoder::new(sui.clone());
+    let fields = dynamic_fields::decode_fields(&sui, &decoder, &fields).await?;
+
+    println!("{} dynamic fields of {}:", fields.len(), args.id);
+    for field in &fields {
+        let kind = match field.kind {
+            DynamicFieldType::DynamicField => "field",
+            DynamicFieldType::DynamicObject => "object",
+        };
+        println!(
+            "{} ({}) [{kind}] {}\n  {}: {}",
+            field.name, field.name_type, field.object_id, field.value_type, field.value
+        );
+    }
+    Ok(())
+}
+
+async fn decode_many(args: DecodeManyArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let ids = read_ids_file(&args.ids_file)?;
+    let sui = global.setup_for_read().await?;
+    let decoder = MoveValueDecoder::new(sui.clone());
+    let results =
+        decode::decode_objects(&sui, &decoder, &ids, &args.object_type, args.concurrency).await?;
+
+    let mut failures = 0;
+    let rows: Vec<_> = ids
+        .iter()
+        .zip(results)
+        .map(|(id, result)| match result {
+            Ok(value) => serde_json::json!({ "id": id, "value": value }),
+            Err(e) => {
+                failures += 1;
+                serde_json::json!({ "id": id, "error": format!("{e:#}") })
+            }
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&rows)?);
+    if failures > 0 {
+        eprintln!("{failures} of {} objects could not be decoded", ids.len());
+    }
+    Ok(())
+}
+
+/// How many scripts may be running inside each other, which stops a script that runs itself.
+const MAX_SCRIPT_DEPTH: usize = 8;
+
+/// Number of scripts currently running, counting those that run other scripts.
+static SCRIPT_DEPTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts a running script in [`SCRIPT_DEPTH`] until it is dropped.
+struct ScriptDepthGuard;
+
+impl ScriptDepthGuard {
+    fn enter(file: &Path) -> Result<Self, anyhow::Error> {
+        if SCRIPT_DEPTH.fetch_add(1, Ordering::SeqCst) >= MAX_SCRIPT_DEPTH {
+            SCRIPT_DEPTH.fetch_sub(1, Ordering::SeqCst);
+            anyhow::bail!(
+                "Cannot run {}: scripts are nested more than {MAX_SCRIPT_DEPTH} deep, does a script run itself?",
+                file.display()
+            );
+        }
+        Ok(Self)
+    }
+}
+
+impl Drop for ScriptDepthGuard {
+    fn drop(&mut self) {
+        SCRIPT_DEPTH.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+async fn script(args: ScriptArgs, global: &GlobalArgs) -> Result<(), anyhow::Error> {
+    let _depth = ScriptDepthGuard::enter(&args.file)?;
+    let contents = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("Cannot read {}", args.file.display()))?;
+    let lines = script::parse(&contents)?;
+
+    let mut bindings = Bindings::default();
+    let mut failures = 0;
+    for line in &lines {
+        println!("==> {}: {}", line.number, line.words.join(" "));
+        if let Err(e) = run_script_line(line, &mut bindings, global).await {
+            let e = e.context(format!(
+                "Line {} of {} failed",
+                line.number,
+                args.file.display()
+            ));
+            if !args.continue_on_error {
+                return Err(e);
+            }
+            println!("{e:#}");
+            failures += 1;
+        }
+    }
+    if failures > 0 {
+        anyhow::bail!("{failures} of {} commands failed", lines.len());
+    }
+    Ok(())
+}
+
+async fn run_script_line(
+    line: &ScriptLine,
+    bindings: &mut Bindings,
+    global: &GlobalArgs,
+) -> Result<(), anyhow::Error> {
+    let words = line
+        .words
+        .iter()
+        .map(|word| bindings.substitute(word))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut command = ScriptCommand::try_parse_from(words)?.command;
+
+    // Bound commands write the IDs they create to a file, which is read back afterwards
+    let ids_file = match &line.binding {
+        Some(_) => {
+            let path = std::env::temp_dir().join(format!(
+                "sui-connect-script-{}-{}.ids",
+                std::process::id(),
+                line.number
+            ));
+            let created_ids = match &mut command {
+                Commands::CreateRoom(args) => &mut args.created_ids,
+                Commands::BatchCreateRoom(args) => &mut args.created_ids,
+                _ => anyhow::bail!(
+                    "Only create-room and batch-create-room report the objects they create"
+                ),
+            };
+            if created_ids.created_ids_out.is_some() {
+                anyhow::bail!("A bound command cannot also have --created-ids-out");
+            }
+            created_ids.created_ids_out = Some(path.clone());
+            created_ids.created_ids_format = IdsFormat::Lines;
+            Some(path)
+        }
+        None => None,
+    };
+
+    // Scripts can run scripts, so the recursive call is boxed
+    let future: LocalBoxFuture<'_, _> = Box::pin(run(command, global));
+    future.await?;
+
+    if let (Some(name), Some(path)) = (&line.binding, ids_file) {
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Cannot read {}", path.display()))?;
+        std::fs::remove_file(&path)?;
+        let ids = batch::parse_object_ids(&contents)?;
+        let rendered: Vec<_> = ids.iter().map(ObjectID::to_string).collect();
+        println!("${name} = {}", rendered.join(", "));
+        bindings.bind(name.clone(), ids);
+    }
+    Ok(())
+}
+
+async fn validate_config(
+    args: &ValidateConfigArgs,
+    global: &GlobalArgs,
+) -> Result<(), anyhow::Error> {
+    let path = global
+        .config
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(config::DEFAULT_CONFIG_FILE));
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Cannot read {}", path.display()))?;
+
+    // The checks below only use the valid keys, so they run whatever else is wrong
+    let check = Config::check(&contents);
+    let invalid = |key: &str| check.invalid_keys.iter().any(|invalid| invalid == key);
+    let mut problems = check.problems.clone();
+    if let Some(settings) = &check.config {
+        if let Some(sender) = &settings.default_sender {
+            if let Err(e) = utils::keystore_address(sender) {
+                problems.push(format!("default_sender: {e}"));
+            }
+        }
+        // Without a valid network, the objects would be looked up on the wrong one
+        if args.online && (global.network.is_some() || !invalid("network")) {
+            let network = global.network.or(settings.network).unwrap_or_default();
+            let deployment = settings.deployment();
+            let mut objects = vec![("package", deployment.package), ("room", deployment.room)];
+            if let Some(card_type) = &settings.card_type {
+                objects.push(("card_type package", ObjectID::from(card_type.address)));
+            }
+            if let Some(room_type) = &settings.room_type {
+                objects.push(("room_type package", ObjectID::from(room_type.address)));
+            }
+            // An invalid key is already reported, its default is not worth checking
+            objects.retain(|(key, _)| !invalid(key.split(' ').next().unwrap()));
+            let rpc_url = global.rpc_url.as_deref().unwrap_or(network.rpc_url());
+            let sui = utils::setup_for_read_at(network, rpc_url).await?;
+            let ids: Vec<_> = objects.iter().map(|(_, id)| *id).collect();
+            let statuses = game::object_statuses(&sui, &ids).await?;
+            for ((key, id), status) in objects.iter().zip(statuses) {
+                if !matches!(status, game::ObjectStatus::Exists { .. }) {
+                    problems.push(format!("{key}: {id} {status} on {network}"));
+                }
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        println!("{} is valid", path.display());
+        return Ok(());
+    }
+    println!("{}:", path.display());
+    for problem in &problems {
+        println!("  {problem}");
+    }
+    anyhow::bail!("Found {} problems in {}", problems.len(), path.display())
+}
+
+/// Print the inputs and commands of a programmable transaction, one per line.
+fn print_ptb(pt: &ProgrammableTransaction) {
+    println!("Inputs:");
+    for (i, input) in pt.inputs.iter().enumerate() {
+        println!("  {i}: {}", plan::describe_input(input));
+    }
+    println!("Commands:");
+    for (i, command) in pt.commands.iter().enumerate() {
+        println!("  {i}: {}", plan::describe_command(command));
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::anyhow;
+use serde_json::json;
+use sui_json_rpc_types::{
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct,
+};
+use sui_sdk::{types::base_types::ObjectID, SuiClient};
+use tokio::sync::OnceCell;
+
+use crate::transcript;
+
+/// The normalized modules of a package, by module name.
+pub type PackageModules = BTreeMap<String, SuiMoveNormalizedModule>;
+
+/// Fetches the normalized Move modules of packages and keeps them, so that each package is
+/// only queried once however many of its functions and structs are looked up.
+///
+/// Callers looking up the same package concurrently wait for a single request.
+pub struct ModuleCache {
+    sui: SuiClient,
+    packages: Mutex<HashMap<ObjectID, Arc<OnceCell<Arc<PackageModules>>>>>,
+}
+
+impl ModuleCache {
+    pub fn new(sui: SuiClient) -> Self {
+        Self {
+            sui,
+            packages: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Every module of a package, fetched on first use.
+    ///
+    /// A failed fetch is not cached: the next lookup of the package tries again.
+    pub async fn package(&self, package: ObjectID) -> Result<Arc<PackageModules>, anyhow::Error> {
+        let cell = self
+            .packages
+            .lock()
+            .unwrap()
+            .entry(package)
+            .or_default()
+            .clone();
+        let modules = cell.get_or_try_init(|| self.fetch(package)).await?;
+        Ok(modules.clone())
+    }
+
+    async fn fetch(&self, package: ObjectID) -> Result<Arc<PackageModules>, anyhow::Error> {
+        let result = self
+            .sui
+            .read_api()
+            .get_normalized_move_modules_by_package(package)
+            .await
+            .map_err(anyhow::Error::from);
+        // The module names are enough to follow the transcript, the signatures would dwarf it
+        transcript::record_rpc(
+            "sui_getNormalizedMoveModulesByPackage",
+            || json!({ "package": package }),
+            result
+                .as_ref()
+                .map(|modules| modules.keys().collect::<Vec<_>>()),
+        );
+        Ok(Arc::new(result?))
+    }
+
+    /// The signature of a function exposed by a module of `package`.
+    pub async fn function(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+    ) -> Result<SuiMoveNormalizedFunction, anyhow::Error> {
+        self.package(package)
+            .await?
+            .get(module)
+            .and_then(|normalized| normalized.exposed_functions.get(function))
+            .cloned()
+            .ok_or_else(|| anyhow!("{package}::{module}::{function} does not exist"))
+    }
+
+    /// The definition of a struct declared by a module of `package`.
+    pub async fn struct_definition(
+        &self,
+        package: ObjectID,
+        module: &str,
+        name: &str,
+    ) -> Result<SuiMoveNormalizedStruct, anyhow::Error> {
+        self.package(package)
+            .await?
+            .get(module)
+            .and_then(|normalized| normalized.structs.get(name))
+            .cloned()
+            .ok_or_else(|| anyhow!("{name} is not defined in {package}::{module}"))
+    }
+}
@@ -0,0 +1,103 @@
+use std::{collections::HashMap, str::FromStr, sync::Mutex};
+
+use anyhow::{anyhow, bail};
+use sui_sdk::types::base_types::SuiAddress;
+
+use crate::{error::GameError, transcript};
+
+/// A recipient given either as a `0x` address or as a SuiNS name such as `alice.sui`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Recipient {
+    Address(SuiAddress),
+    Name(String),
+}
+
+impl FromStr for Recipient {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("0x") {
+            return Ok(Recipient::Address(SuiAddress::from_str(s)?));
+        }
+        if s.ends_with(".sui") || s.starts_with('@') {
+            return Ok(Recipient::Name(s.to_lowercase()));
+        }
+        bail!("`{s}` is neither a 0x address nor a SuiNS name")
+    }
+}
+
+impl Recipient {
+    /// The address of the recipient, looking names up with `resolver`.
+    pub async fn resolve(&self, resolver: &NameResolver) -> Result<SuiAddress, anyhow::Error> {
+        match self {
+            Recipient::Address(address) => Ok(*address),
+            Recipient::Name(name) => resolver.resolve(name).await,
+        }
+    }
+}
+
+/// Resolves SuiNS names through the `suix_resolveNameServiceAddress` RPC method.
+///
+/// Resolutions are cached, so a name used several times in one run is only looked up once.
+pub struct NameResolver {
+    rpc_url: String,
+    cache: Mutex<HashMap<String, SuiAddress>>,
+}
+
+impl NameResolver {
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The address a SuiNS name points to, failing if the name does not resolve.
+    pub async fn resolve(&self, name: &str) -> Result<SuiAddress, anyhow::Error> {
+        if let Some(address) = self.cache.lock().unwrap().get(name) {
+            return Ok(*address);
+        }
+
+        let response = self.lookup(name).await;
+        transcript::record_rpc(
+            "suix_resolveNameServiceAddress",
+            || serde_json::json!({ "name": name }),
+            response.as_ref(),
+        );
+        let response = response?;
+        if let Some(error) = response.get("error") {
+            bail!("Cannot resolve {name}: {error}");
+        }
+
+        let address = match response.get("result") {
+            Some(serde_json::Value::String(address)) => SuiAddress::from_str(address)
+                .map_err(|e| anyhow!("{name} resolved to an invalid address: {e}"))?,
+            _ => {
+                return Err(GameError::UnresolvedName {
+                    name: name.to_string(),
+                }
+                .into())
+            }
+        };
+        self.cache.lock().unwrap().insert(name.to_string(), address);
+        Ok(address)
+    }
+
+    async fn lookup(&self, name: &str) -> Result<serde_json::Value, anyhow::Error> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "suix_resolveNameServiceAddress",
+            "params": [name],
+        });
+        let response = reqwest::Client::new()
+            .post(&self.rpc_url)
+            .header("Content-Type", "application/json")
+            .body(request.to_string())
+            .send()
+            .await?
+            .text()
+            .await?;
+        Ok(serde_json::from_str(&response)?)
+    }
+}
@@ -0,0 +1,98 @@
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+use sui_sdk::SuiClientBuilder;
+
+use crate::transcript;
+
+/// Round-trip latency and reliability of an RPC endpoint over a few requests.
+#[derive(Clone, Debug)]
+pub struct PingStats {
+    pub url: String,
+    /// Number of requests sent.
+    pub samples: usize,
+    /// Latency of each request that succeeded.
+    pub latencies: Vec<Duration>,
+    /// The last error, if any request failed.
+    pub error: Option<String>,
+}
+
+impl PingStats {
+    pub fn min(&self) -> Option<Duration> {
+        self.latencies.iter().min().copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.latencies.iter().max().copied()
+    }
+
+    pub fn avg(&self) -> Option<Duration> {
+        let count = u32::try_from(self.latencies.len())
+            .ok()
+            .filter(|&n| n > 0)?;
+        Some(self.latencies.iter().sum::<Duration>() / count)
+    }
+
+    /// Share of the requests that succeeded, between 0 and 1.
+    pub fn success_rate(&self) -> f64 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        self.latencies.len() as f64 / self.samples as f64
+    }
+}
+
+/// Time `samples` calls to `sui_getChainIdentifier` on the endpoint at `url`.
+///
+/// An endpoint that cannot be reached is reported with no successful samples rather than
+/// as an error, so one bad URL does not stop the others from being measured.
+pub async fn ping(url: &str, samples: usize, timeout: Duration) -> PingStats {
+    let mut stats = PingStats {
+        url: url.to_string(),
+        samples,
+        latencies: vec![],
+        error: None,
+    };
+    let sui = match SuiClientBuilder::default()
+        .request_timeout(timeout)
+        .build(url)
+        .await
+    {
+        Ok(sui) => sui,
+        Err(e) => {
+            stats.error = Some(e.to_string());
+            return stats;
+        }
+    };
+
+    for _ in 0..samples {
+        let start = Instant::now();
+        let result = sui
+            .read_api()
+            .get_chain_identifier()
+            .await
+            .map_err(anyhow::Error::from);
+        let latency = start.elapsed();
+        transcript::record_rpc(
+            "sui_getChainIdentifier",
+            || json!({ "url": transcript::redact_url(url) }),
+            result.as_ref(),
+        );
+        match result {
+            Ok(_) => stats.latencies.push(latency),
+            Err(e) => stats.error = Some(e.to_string()),
+        }
+    }
+    stats
+}
+
+/// Sort endpoints from best to worst: highest success rate first, then lowest average latency.
+pub fn rank(stats: &mut [PingStats]) {
+    stats.sort_by(|a, b| {
+        b.success_rate().total_cmp(&a.success_rate()).then_with(|| {
+            a.avg()
+                .unwrap_or(Duration::MAX)
+                .cmp(&b.avg().unwrap_or(Duration::MAX))
+        })
+    });
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+
+use sui_sdk::types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    transaction::{
+        Argument, CallArg, Command, ObjectArg, ProgrammableTransaction, TransactionData,
+    },
+};
+
+/// A fully resolved transaction that has not been signed or submitted yet.
+///
+/// Produced by [`crate::GameClient::plan`]; every field can be inspected or changed
+/// before handing the plan to [`crate::GameClient::execute_plan`].
+#[derive(Clone, Debug)]
+pub struct TransactionPlan {
+    pub sender: SuiAddress,
+    /// Inputs of the programmable transaction, referenced by `Argument::Input(index)`.
+    pub inputs: Vec<CallArg>,
+    pub commands: Vec<Command>,
+    pub gas_coin: ObjectRef,
+    /// Gas budget in MIST.
+    pub gas_budget: u64,
+    /// Gas price in MIST per unit of gas.
+    pub gas_price: u64,
+}
+
+impl TransactionPlan {
+    pub fn new(
+        sender: SuiAddress,
+        pt: ProgrammableTransaction,
+        gas_coin: ObjectRef,
+        gas_budget: u64,
+        gas_price: u64,
+    ) -> Self {
+        Self {
+            sender,
+            inputs: pt.inputs,
+            commands: pt.commands,
+            gas_coin,
+            gas_budget,
+            gas_price,
+        }
+    }
+
+    /// The transaction data to sign for this plan.
+    pub fn transaction_data(&self) -> TransactionData {
+        let pt = ProgrammableTransaction {
+            inputs: self.inputs.clone(),
+            commands: self.commands.clone(),
+        };
+        TransactionData::new_programmable(
+            self.sender,
+            vec![self.gas_coin],
+            pt,
+            self.gas_budget,
+            self.gas_price,
+        )
+    }
+
+    /// Whether an owned or shared object input of the plan is the object `id`.
+    pub fn uses_object(&self, id: ObjectID) -> bool {
+        self.inputs.iter().any(|input| match input {
+            CallArg::Object(ObjectArg::ImmOrOwnedObject((object, ..)))
+            | CallArg::Object(ObjectArg::Receiving((object, ..)))
+            | CallArg::Object(ObjectArg::SharedObject { id: object, .. }) => *object == id,
+            CallArg::Pure(_) => false,
+        })
+    }
+
+    /// MIST that the plan's commands split from the gas coin, such as a transfer paid out
+    /// of it. Only amounts passed as plain `u64` inputs are counted.
+    pub fn split_from_gas(&self) -> u64 {
+        self.commands
+            .iter()
+            .filter_map(|command| match command {
+                Command::SplitCoins(Argument::GasCoin, amounts) => Some(amounts),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|amount| match amount {
+                Argument::Input(i) => self.inputs.get(usize::from(*i)),
+                _ => None,
+            })
+            .filter_map(|input| match input {
+                CallArg::Pure(bytes) => bcs::from_bytes::<u64>(bytes).ok(),
+                _ => None,
+            })
+            .fold(0, u64::saturating_add)
+    }
+}
+
+impl fmt::Display for TransactionPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Sender: {}", self.sender)?;
+        writeln!(f, "Inputs:")?;
+        for (i, input) in self.inputs.iter().enumerate() {
+            writeln!(f, "  {i}: {}", describe_input(input))?;
+        }
+        writeln!(f, "Commands:")?;
+        for (i, command) in self.commands.iter().enumerate() {
+            writeln!(f, "  {i}: {}", describe_command(command))?;
+        }
+        writeln!(
+            f,
+            "Gas coin: {} (version {})",
+            self.gas_coin.0,
+            self.gas_coin.1.value()
+        )?;
+        writeln!(f, "Gas budget: {} MIST", self.gas_budget)?;
+        write!(f, "Gas price: {} MIST", self.gas_price)
+    }
+}
+
+/// Render a programmable transaction input on a single line.
+pub fn describe_input(input: &CallArg) -> String {
+    match input {
+        CallArg::Pure(bytes) => format!("Pure 0x{}", hex::encode(bytes)),
+        CallArg::Object(ObjectArg::ImmOrOwnedObject((id, version, _))) => {
+            format!("Owned object {id} (version {})", version.value())
+        }
+        CallArg::Object(ObjectArg::SharedObject {
+            id,
+            initial_shared_version,
+            mutable,
+        }) => format!(
+            "Shared object {id} (initial version {}, {})",
+            initial_shared_version.value(),
+            if *mutable { "mutable" } else { "immutable" }
+        ),
+        CallArg::Object(ObjectArg::Receiving((id, version, _))) => {
+            format!("Receiving object {id} (version {})", version.value())
+        }
+    }
+}
+
+/// Render a programmable transaction command on a single line.
+pub fn describe_command(command: &Command) -> String {
+    match command {
+        Command::MoveCall(call) => {
+            let type_arguments = if call.type_arguments.is_empty() {
+                String::new()
+            } else {
+                let types: Vec<_> = call.type_arguments.iter().map(|t| t.to_string()).collect();
+                format!("<{}>", types.join(", "))
+            };
+            format!(
+                "MoveCall {}::{}::{}{type_arguments}({})",
+                call.package,
+                call.module,
+                call.function,
+                describe_arguments(&call.arguments)
+            )
+        }
+        Command::TransferObjects(objects, recipient) => format!(
+            "TransferObjects [{}] to {}",
+            describe_arguments(objects),
+            describe_argument(recipient)
+        ),
+        Command::SplitCoins(coin, amounts) => format!(
+            "SplitCoins {} into [{}]",
+            describe_argument(coin),
+            describe_arguments(amounts)
+        ),
+        Command::MergeCoins(target, sources) => format!(
+            "MergeCoins [{}] into {}",
+            describe_arguments(sources),
+            describe_argument(target)
+        ),
+        Command::MakeMoveVec(element_type, elements) => match element_type {
+            Some(element_type) => format!(
+                "MakeMoveVec<{element_type}> [{}]",
+                describe_arguments(elements)
+            ),
+            None => format!("MakeMoveVec [{}]", describe_arguments(elements)),
+        },
+        Command::Publish(modules, dependencies) => format!(
+            "Publish {} modules with {} dependencies",
+            modules.len(),
+            dependencies.len()
+        ),
+        Command::Upgrade(modules, _, package, ticket) => format!(
+            "Upgrade {package} with {} modules using ticket {}",
+            modules.len(),
+            describe_argument(ticket)
+        ),
+    }
+}
+
+fn describe_arguments(arguments: &[Argument]) -> String {
+    arguments
+        .iter()
+        .map(describe_argument)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_argument(argument: &Argument) -> String {
+    match argument {
+        Argument::GasCoin => "GasCoin".to_string(),
+        Argument::Input(i) => format!("Input({i})"),
+        Argument::Result(i) => format!("Result({i})"),
+        Argument::NestedResult(i, j) => format!("NestedResult({i}, {j})"),
+    }
+}
@@ -0,0 +1,134 @@
+use futures::future::BoxFuture;
+use serde_json::json;
+use sui_sdk::{types::base_types::SuiAddress, SuiClient};
+
+use crate::transcript;
+
+/// Total balance of one coin type held by an address.
+#[derive(Clone, Debug)]
+pub struct Holding {
+    pub coin_type: String,
+    pub symbol: Option<String>,
+    /// Number of decimals of the coin, from its metadata (9 for SUI).
+    pub decimals: Option<u8>,
+    /// Balance in the smallest unit of the coin.
+    pub balance: u128,
+}
+
+impl Holding {
+    /// The balance in whole coins, if the number of decimals of the coin is known.
+    pub fn amount(&self) -> Option<f64> {
+        self.decimals
+            .map(|decimals| self.balance as f64 / 10f64.powi(decimals.into()))
+    }
+}
+
+/// List every coin type held by an address with its total balance.
+///
+/// Only the full node is queried, so this works without any price source.
+pub async fn holdings(sui: &SuiClient, owner: SuiAddress) -> Result<Vec<Holding>, anyhow::Error> {
+    let balances = sui
+        .coin_read_api()
+        .get_all_balances(owner)
+        .await
+        .map_err(anyhow::Error::from);
+    transcript::record_rpc(
+        "suix_getAllBalances",
+        || json!({ "owner": owner }),
+        balances.as_ref(),
+    );
+
+    let balances = balances?;
+    let mut holdings = Vec::with_capacity(balances.len());
+    for balance in balances {
+        let metadata = sui
+            .coin_read_api()
+            .get_coin_metadata(balance.coin_type.clone())
+            .await
+            .map_err(anyhow::Error::from);
+        transcript::record_rpc(
+            "suix_getCoinMetadata",
+            || json!({ "coin_type": balance.coin_type }),
+            metadata.as_ref(),
+        );
+        let metadata = metadata?;
+        holdings.push(Holding {
+            symbol: metadata.as_ref().map(|metadata| metadata.symbol.clone()),
+            decimals: metadata.as_ref().map(|metadata| metadata.decimals),
+            coin_type: balance.coin_type,
+            balance: balance.total_balance,
+        });
+    }
+    holdings.sort_by(|a, b| a.coin_type.cmp(&b.coin_type));
+    Ok(holdings)
+}
+
+/// A source of approximate fiat prices for coin types.
+pub trait PriceSource: Send + Sync {
+    /// The approximate price in USD of one whole coin, or `None` if the source has no price.
+    fn usd_price<'a>(
+        &'a self,
+        coin_type: &'a str,
+    ) -> BoxFuture<'a, Result<Option<f64>, anyhow::Error>>;
+}
+
+/// Prices fetched over HTTP from a URL template.
+///
+/// `{coin_type}` in the template is replaced by the coin type. The response must be JSON,
+/// either a bare number or an object with a numeric `usd` or `price` field.
+pub struct HttpPriceSource {
+    url_template: String,
+}
+
+impl HttpPriceSource {
+    pub fn new(url_template: impl Into<String>) -> Self {
+        Self {
+            url_template: url_template.into(),
+        }
+    }
+}
+
+impl PriceSource for HttpPriceSource {
+    fn usd_price<'a>(
+        &'a self,
+        coin_type: &'a str,
+    ) -> BoxFuture<'a, Result<Option<f64>, anyhow::Error>> {
+        Box::pin(async move {
+            let url = self.url_template.replace("{coin_type}", coin_type);
+            let response = reqwest::get(&url).await?;
+            if !response.status().is_success() {
+                return Ok(None);
+            }
+            let body: serde_json::Value = serde_json::from_str(&response.text().await?)?;
+            let price = match &body {
+                serde_json::Value::Number(price) => price.as_f64(),
+                serde_json::Value::Object(fields) => fields
+                    .get("usd")
+                    .or_else(|| fields.get("price"))
+                    .and_then(serde_json::Value::as_f64),
+                _ => None,
+            };
+            Ok(price)
+        })
+    }
+}
+
+/// Approximate USD value of each holding, in the same order as `holdings`.
+/// Holdings without a known price or number of decimals are `None`.
+pub async fn usd_values(
+    holdings: &[Holding],
+    prices: &dyn PriceSource,
+) -> Result<Vec<Option<f64>>, anyhow::Error> {
+    let mut values = Vec::with_capacity(holdings.len());
+    for holding in holdings {
+        let value = match holding.amount() {
+            Some(amount) => prices
+                .usd_price(&holding.coin_type)
+                .await?
+                .map(|price| price * amount),
+            None => None,
+        };
+        values.push(value);
+    }
+    Ok(values)
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use serde::Serialize;
+use sui_sdk::types::{
+    base_types::{ObjectID, SuiAddress},
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{Argument, CallArg, Command, ObjectArg, ProgrammableTransaction},
+};
+
+/// A programmable transaction builder that adds each object as an input only once.
+///
+/// Sui rejects a transaction that lists the same owned object as two inputs, so referencing
+/// an object that is already an input returns its existing `Argument::Input` instead. The
+/// same object given at two different versions is an error, and a shared object referenced
+/// both mutably and immutably becomes a single mutable input.
+///
+/// With [`PtbBuilder::with_dedup`] set to false, referencing an object twice is an error
+/// instead, which catches builders that are never meant to reuse an object.
+///
+/// Builders can also record the steps they go through with [`PtbBuilder::step`], so the
+/// construction of a transaction can be reviewed next to the commands it produced.
+pub struct PtbBuilder {
+    inner: ProgrammableTransactionBuilder,
+    objects: HashMap<ObjectID, (Argument, ObjectArg)>,
+    dedup: bool,
+    steps: Vec<String>,
+}
+
+impl Default for PtbBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PtbBuilder {
+    pub fn new() -> Self {
+        Self {
+            inner: ProgrammableTransactionBuilder::new(),
+            objects: HashMap::new(),
+            dedup: true,
+            steps: vec![],
+        }
+    }
+
+    /// Whether an object referenced again reuses its input (the default) or is rejected.
+    pub fn with_dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Record a step of the construction, in the order it happens.
+    pub fn step(&mut self, description: impl Into<String>) {
+        self.steps.push(description.into());
+    }
+
+    /// The steps recorded so far.
+    pub fn steps(&self) -> &[String] {
+        &self.steps
+    }
+
+    /// Add an object input, or return the input that already holds the object.
+    pub fn object(&mut self, object: ObjectArg) -> Result<Argument, anyhow::Error> {
+        let id = object_id(&object);
+        let Some((argument, existing)) = self.objects.get(&id).copied() else {
+            let argument = self.inner.obj(object)?;
+            self.objects.insert(id, (argument, object));
+            return Ok(argument);
+        };
+        if !self.dedup {
+            bail!("Object {id} is already an input of the transaction");
+        }
+
+        match (existing, object) {
+            (existing, object) if existing == object => Ok(argument),
+            (
+                ObjectArg::SharedObject {
+                    initial_shared_version: existing_version,
+                    mutable: existing_mutable,
+                    ..
+                },
+                ObjectArg::SharedObject {
+                    initial_shared_version,
+                    mutable,
+                    ..
+                },
+            ) if existing_version == initial_shared_version => {
+                let merged = ObjectArg::SharedObject {
+                    id,
+                    initial_shared_version,
+                    mutable: existing_mutable || mutable,
+                };
+                let argument = self.inner.obj(merged)?;
+                self.objects.insert(id, (argument, merged));
+                Ok(argument)
+            }
+            (existing, object) => {
+                bail!("Object {id} is referenced as both {existing:?} and {object:?}")
+            }
+        }
+    }
+
+    /// Add a transaction input, deduplicating objects like [`PtbBuilder::object`].
+    pub fn input(&mut self, input: CallArg) -> Result<Argument, anyhow::Error> {
+        match input {
+            CallArg::Object(object) => self.object(object),
+            CallArg::Pure(bytes) => Ok(self.inner.pure_bytes(bytes, false)),
+        }
+    }
+
+    /// Add a pure value, encoded with BCS.
+    pub fn pure<T: Serialize>(&mut self, value: T) -> Result<Argument, anyhow::Error> {
+        self.inner.pure(value)
+    }
+
+    pub fn command(&mut self, command: Command) -> Argument {
+        self.inner.command(command)
+    }
+
+    pub fn transfer_args(&mut self, recipient: SuiAddress, objects: Vec<Argument>) {
+        self.inner.transfer_args(recipient, objects)
+    }
+
+    pub fn pay_sui(
+        &mut self,
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<u64>,
+    ) -> Result<(), anyhow::Error> {
+        self.inner.pay_sui(recipients, amounts)
+    }
+
+    pub fn finish(self) -> ProgrammableTransaction {
+        self.inner.finish()
+    }
+}
+
+/// The ID of the object an input refers to.
+pub(crate) fn object_id(object: &ObjectArg) -> ObjectID {
+    match object {
+        ObjectArg::ImmOrOwnedObject((id, _, _)) | ObjectArg::Receiving((id, _, _)) => *id,
+        ObjectArg::SharedObject { id, .. } => *id,
+    }
+}
@@ -0,0 +1,109 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{StructTag, TypeTag},
+    u256::U256,
+};
+use serde_json::Value;
+
+/// Encode a value as the BCS bytes of a pure argument of the given Move type.
+///
+/// Integers may be JSON numbers or strings, addresses and IDs are `0x` strings, and
+/// vectors are JSON arrays (a `vector<u8>` may also be a `0x` hex string). `String`s of
+/// `0x1::string` and `0x1::ascii` are JSON strings and an `Option<T>` is `null` or a `T`.
+pub fn encode(type_tag: &TypeTag, value: &Value) -> Result<Vec<u8>, anyhow::Error> {
+    Ok(match type_tag {
+        TypeTag::Bool => bcs::to_bytes(&value.as_bool().ok_or_else(|| expected("a bool", value))?)?,
+        TypeTag::U8 => bcs::to_bytes(&u8::try_from(integer(value)?)?)?,
+        TypeTag::U16 => bcs::to_bytes(&u16::try_from(integer(value)?)?)?,
+        TypeTag::U32 => bcs::to_bytes(&u32::try_from(integer(value)?)?)?,
+        TypeTag::U64 => bcs::to_bytes(&u64::try_from(integer(value)?)?)?,
+        TypeTag::U128 => bcs::to_bytes(&integer(value)?)?,
+        TypeTag::U256 => {
+            let n = match value {
+                Value::String(s) => U256::from_str(s)?,
+                Value::Number(n) => {
+                    U256::from(n.as_u64().ok_or_else(|| expected("a u256", value))?)
+                }
+                _ => return Err(expected("a u256", value)),
+            };
+            bcs::to_bytes(&n)?
+        }
+        TypeTag::Address => bcs::to_bytes(&address(value)?)?,
+        TypeTag::Vector(element) => match (element.as_ref(), value) {
+            (TypeTag::U8, Value::String(s)) => {
+                let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s))?;
+                bcs::to_bytes(&bytes)?
+            }
+            (element, Value::Array(elements)) => {
+                let mut bytes = uleb128(elements.len());
+                for e in elements {
+                    bytes.extend(encode(element, e)?);
+                }
+                bytes
+            }
+            _ => return Err(expected("an array", value)),
+        },
+        TypeTag::Struct(tag) => encode_struct(tag, value)?,
+        TypeTag::Signer => bail!("A signer cannot be passed as a pure argument"),
+    })
+}
+
+fn encode_struct(tag: &StructTag, value: &Value) -> Result<Vec<u8>, anyhow::Error> {
+    let (module, name) = (tag.module.as_str(), tag.name.as_str());
+    if tag.address == AccountAddress::ONE
+        && matches!(module, "string" | "ascii")
+        && name == "String"
+    {
+        let s = value.as_str().ok_or_else(|| expected("a string", value))?;
+        return Ok(bcs::to_bytes(s)?);
+    }
+    if tag.address == AccountAddress::ONE && module == "option" && name == "Option" {
+        let [element] = tag.type_params.as_slice() else {
+            bail!("{tag} must have exactly one type argument");
+        };
+        return Ok(match value {
+            Value::Null => vec![0],
+            value => [vec![1], encode(element, value)?].concat(),
+        });
+    }
+    if tag.address == AccountAddress::TWO && module == "object" && name == "ID" {
+        return Ok(bcs::to_bytes(&address(value)?)?);
+    }
+    bail!("{tag} cannot be passed as a pure argument")
+}
+
+fn integer(value: &Value) -> Result<u128, anyhow::Error> {
+    match value {
+        Value::Number(n) => n.as_u64().map(u128::from),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| expected("an unsigned integer", value))
+}
+
+fn address(value: &Value) -> Result<AccountAddress, anyhow::Error> {
+    let s = value
+        .as_str()
+        .ok_or_else(|| expected("a 0x address", value))?;
+    Ok(AccountAddress::from_hex_literal(s)?)
+}
+
+fn uleb128(mut n: usize) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn expected(what: &str, value: &Value) -> anyhow::Error {
+    anyhow!("Expected {what}, got {value}")
+}
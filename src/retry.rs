@@ -0,0 +1,247 @@
+use std::{future::Future, num::NonZeroU32, time::Duration};
+
+use serde::Deserialize;
+use tracing::warn;
+
+/// How often and how long a class of RPC calls is attempted.
+///
+/// Only attempts that time out or fail to reach the full node are retried, see
+/// [`is_transient`]; an answer from the full node, such as an object of the wrong type,
+/// would be the same on every attempt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub attempts: u32,
+    /// Time allowed for each attempt, in seconds.
+    pub timeout_secs: u64,
+    /// Delay before the second attempt, in milliseconds; it grows linearly with each retry.
+    pub backoff_ms: u64,
+}
+
+impl RetryPolicy {
+    /// Object reads and queries have no side effects and can be retried freely.
+    pub const READ: RetryPolicy = RetryPolicy {
+        attempts: 3,
+        timeout_secs: 30,
+        backoff_ms: 500,
+    };
+
+    /// Transaction submission. Retries re-submit the same signed transaction, after first
+    /// checking whether the previous attempt landed, so a transaction never runs twice.
+    pub const WRITE: RetryPolicy = RetryPolicy {
+        attempts: 2,
+        timeout_secs: 120,
+        backoff_ms: 1_000,
+    };
+
+    /// Event subscriptions, which are re-established when the connection drops.
+    pub const SUBSCRIPTION: RetryPolicy = RetryPolicy {
+        attempts: 5,
+        timeout_secs: 30,
+        backoff_ms: 1_000,
+    };
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    /// The delay to wait after the given (1-based) failed attempt.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        Duration::from_millis(self.backoff_ms.saturating_mul(attempt.into()))
+    }
+
+    /// Run `operation` until it succeeds, fails with an error that is not transient, or has
+    /// failed or timed out `attempts` times, returning the last error.
+    pub async fn run<T, F, Fut>(&self, mut operation: F) -> Result<T, anyhow::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, anyhow::Error>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let result = match tokio::time::timeout(self.timeout(), operation()).await {
+                Ok(result) => result,
+                Err(_) => Err(TimedOut {
+                    secs: self.timeout_secs,
+                }
+                .into()),
+            };
+            match result {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt >= self.attempts || !is_transient(&e) => return Err(e),
+                Err(e) => {
+                    warn!("Attempt {attempt} of {} failed: {e}", self.attempts);
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// How often a transaction cancelled because a shared object is congested is planned and
+/// submitted again. Each attempt is a whole transaction, with its own RPC policies, so there
+/// is no timeout here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CongestionPolicy {
+    /// Total number of attempts, including the first one.
+    pub attempts: u32,
+    /// Delay before the second attempt, in milliseconds; it grows linearly with each retry.
+    pub backoff_ms: u64,
+}
+
+impl CongestionPolicy {
+    /// Congestion clears as the validators work through the backlog on the object, so the
+    /// backoff is longer than for RPC errors.
+    pub const DEFAULT: CongestionPolicy = CongestionPolicy {
+        attempts: 4,
+        backoff_ms: 2_000,
+    };
+
+    /// The delay to wait after the given (1-based) congested attempt.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        Duration::from_millis(self.backoff_ms.saturating_mul(attempt.into()))
+    }
+}
+
+impl Default for CongestionPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// An attempt that did not finish within the policy's timeout.
+#[derive(Debug, thiserror::Error)]
+#[error("Timed out after {secs}s")]
+pub struct TimedOut {
+    pub secs: u64,
+}
+
+/// Messages of the JSON-RPC client errors raised when the full node could not be reached or
+/// did not answer in time, rather than answering with an error.
+const TRANSIENT_RPC_MARKERS: [&str; 3] = [
+    "Networking or low-level protocol error",
+    "Request timeout",
+    "restart required",
+];
+
+/// Whether an error may go away on its own: an attempt timed out, or the full node or an
+/// HTTP endpoint could not be reached. Errors returned by the full node itself are not.
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        if cause.is::<TimedOut>() {
+            return true;
+        }
+        if let Some(sui_sdk::error::Error::RpcError(e)) = cause.downcast_ref() {
+            let message = e.to_string();
+            return TRANSIENT_RPC_MARKERS
+                .iter()
+                .any(|marker| message.contains(marker));
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return e.is_connect() || e.is_timeout();
+        }
+        false
+    })
+}
+
+/// A retry policy as written in the config file. Fields left out keep the value of the
+/// policy's class, so `[rpc.read]` with only `attempts = 5` keeps the read timeout and backoff.
+/// `attempts` counts the first attempt, so 0 is rejected.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyOverrides {
+    attempts: Option<NonZeroU32>,
+    timeout_secs: Option<u64>,
+    backoff_ms: Option<u64>,
+}
+
+impl PolicyOverrides {
+    fn over(self, base: RetryPolicy) -> RetryPolicy {
+        RetryPolicy {
+            attempts: self.attempts.map_or(base.attempts, NonZeroU32::get),
+            timeout_secs: self.timeout_secs.unwrap_or(base.timeout_secs),
+            backoff_ms: self.backoff_ms.unwrap_or(base.backoff_ms),
+        }
+    }
+}
+
+/// The congestion policy as written in the config file, like [`PolicyOverrides`].
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CongestionOverrides {
+    attempts: Option<NonZeroU32>,
+    backoff_ms: Option<u64>,
+}
+
+impl CongestionOverrides {
+    fn over(self, base: CongestionPolicy) -> CongestionPolicy {
+        CongestionPolicy {
+            attempts: self.attempts.map_or(base.attempts, NonZeroU32::get),
+            backoff_ms: self.backoff_ms.unwrap_or(base.backoff_ms),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RpcPoliciesFile {
+    #[serde(default)]
+    read: PolicyOverrides,
+    #[serde(default)]
+    write: PolicyOverrides,
+    #[serde(default)]
+    subscription: PolicyOverrides,
+    #[serde(default)]
+    congestion: CongestionOverrides,
+}
+
+impl From<RpcPoliciesFile> for RpcPolicies {
+    fn from(file: RpcPoliciesFile) -> Self {
+        Self {
+            read: file.read.over(RetryPolicy::READ),
+            write: file.write.over(RetryPolicy::WRITE),
+            subscription: file.subscription.over(RetryPolicy::SUBSCRIPTION),
+            congestion: file.congestion.over(CongestionPolicy::DEFAULT),
+        }
+    }
+}
+
+/// Retry policies for each class of RPC call, configured under `[rpc]` in the config file.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(from = "RpcPoliciesFile")]
+pub struct RpcPolicies {
+    pub read: RetryPolicy,
+    pub write: RetryPolicy,
+    pub subscription: RetryPolicy,
+    /// Used only when congestion retries are enabled, see
+    /// [`crate::GameClient::with_congestion_retry`].
+    pub congestion: CongestionPolicy,
+}
+
+impl Default for RpcPolicies {
+    fn default() -> Self {
+        Self {
+            read: RetryPolicy::READ,
+            write: RetryPolicy::WRITE,
+            subscription: RetryPolicy::SUBSCRIPTION,
+            congestion: CongestionPolicy::DEFAULT,
+        }
+    }
+}
+
+/// Whether an execution failure or an RPC error says that a shared object of the
+/// transaction is congested.
+///
+/// Validators cancel such transactions before they execute, and full nodes reject them
+/// with too many transactions already pending on the object, so either way nothing ran and
+/// the transaction can be planned and submitted again.
+pub fn is_congestion_error(message: &str) -> bool {
+    const MARKERS: [&str; 3] = [
+        "ExecutionCancelledDueToSharedObjectCongestion",
+        "TooManyTransactionsPendingOnObject",
+        "Too many transactions pending on object",
+    ];
+    MARKERS.iter().any(|marker| message.contains(marker))
+        || message.to_lowercase().contains("shared object congestion")
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail};
+use sui_sdk::types::base_types::ObjectID;
+
+/// A command of a script, with the line it comes from.
+///
+/// Scripts hold one command of the `sui-connect` binary per line, without the binary name.
+/// Blank lines and lines starting with `#` are skipped, and words can be quoted with `"` or
+/// `'`. A line written `<name> = <command>` binds the IDs of the objects the command
+/// creates to `name`, so that later lines can use `$name` for the first of them and
+/// `$name.1`, `$name.2`, ... for the others.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptLine {
+    /// 1-based line number in the script.
+    pub number: usize,
+    pub binding: Option<String>,
+    pub words: Vec<String>,
+}
+
+/// Parse the lines of a script, without substituting bindings yet.
+pub fn parse(contents: &str) -> Result<Vec<ScriptLine>, anyhow::Error> {
+    let mut lines = vec![];
+    for (i, line) in contents.lines().enumerate() {
+        let number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut words = split_words(line).map_err(|e| anyhow!("Line {number}: {e}"))?;
+        let binding = match words.get(1).map(String::as_str) {
+            Some("=") => {
+                let name = words[0].clone();
+                if !is_identifier(&name) {
+                    bail!("Line {number}: `{name}` is not a valid binding name");
+                }
+                words.drain(..2);
+                Some(name)
+            }
+            _ => None,
+        };
+        if words.is_empty() {
+            bail!("Line {number}: expected a command");
+        }
+        lines.push(ScriptLine {
+            number,
+            binding,
+            words,
+        });
+    }
+    Ok(lines)
+}
+
+/// Object IDs bound to names by earlier lines of a script.
+#[derive(Debug, Default)]
+pub struct Bindings {
+    ids: HashMap<String, Vec<ObjectID>>,
+}
+
+impl Bindings {
+    pub fn bind(&mut self, name: String, ids: Vec<ObjectID>) {
+        self.ids.insert(name, ids);
+    }
+
+    /// Replace every `$name` and `$name.<index>` in a word by the bound object ID.
+    pub fn substitute(&self, word: &str) -> Result<String, anyhow::Error> {
+        let mut result = String::with_capacity(word.len());
+        let mut rest = word;
+        while let Some(start) = rest.find('$') {
+            result.push_str(&rest[..start]);
+            let reference = &rest[start + 1..];
+            let name_len = reference
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(reference.len());
+            let name = &reference[..name_len];
+            if name.is_empty() {
+                bail!("Expected a binding name after `$` in `{word}`");
+            }
+            let mut consumed = name_len;
+            let mut index = 0;
+            if let Some(digits) = reference[name_len..].strip_prefix('.') {
+                let digits_len = digits
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(digits.len());
+                if digits_len > 0 {
+                    index = digits[..digits_len].parse()?;
+                    consumed += 1 + digits_len;
+                }
+            }
+
+            let ids = self
+                .ids
+                .get(name)
+                .ok_or_else(|| anyhow!("`${name}` is not bound by an earlier line"))?;
+            let id = ids.get(index).ok_or_else(|| {
+                anyhow!(
+                    "`${name}` only holds {} object IDs, there is no index {index}",
+                    ids.len()
+                )
+            })?;
+            result.push_str(&id.to_string());
+            rest = &reference[consumed..];
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Split a line into words on whitespace, keeping quoted text together.
+fn split_words(line: &str) -> Result<Vec<String>, anyhow::Error> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if let Some(quote) = quote {
+        bail!("Unclosed {quote} quote");
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
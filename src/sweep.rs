@@ -0,0 +1,170 @@
+use std::{ops::RangeInclusive, str::FromStr};
+
+use anyhow::{anyhow, bail, Context};
+use move_core_types::language_storage::TypeTag;
+use sui_json_rpc_types::SuiMoveNormalizedType;
+use sui_sdk::types::{
+    base_types::ObjectID,
+    transaction::{Argument, CallArg, Command},
+};
+
+use crate::{
+    client::{Action, GameClient},
+    pure,
+};
+
+/// Largest number of values swept unless a higher limit is given, since each value costs
+/// a dry run.
+pub const MAX_SWEEP_VALUES: u128 = 1_000;
+
+/// A named range of integer values to sweep, written as `<name>:<start>..<end>` (end
+/// excluded) or `<name>:<start>..=<end>` (end included).
+#[derive(Clone, Debug)]
+pub struct ArgRange {
+    pub name: String,
+    pub values: RangeInclusive<u128>,
+}
+
+impl FromStr for ArgRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, range) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Expected `<name>:<start>..<end>`, got `{s}`"))?;
+        let (start, end) = range
+            .split_once("..")
+            .ok_or_else(|| anyhow!("Expected a range such as `1..10`, got `{range}`"))?;
+        let start: u128 = start
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid start of range `{range}`"))?;
+        let parse_end = |end: &str| -> Result<u128, anyhow::Error> {
+            end.trim()
+                .parse()
+                .with_context(|| format!("Invalid end of range `{range}`"))
+        };
+        let last = match end.strip_prefix('=') {
+            Some(end) => Some(parse_end(end)?),
+            None => parse_end(end)?.checked_sub(1),
+        };
+        let values = match last {
+            Some(last) if last >= start => start..=last,
+            _ => bail!("The range `{range}` is empty"),
+        };
+        Ok(Self {
+            name: name.trim().to_string(),
+            values,
+        })
+    }
+}
+
+impl ArgRange {
+    /// Number of values in the range.
+    pub fn len(&self) -> u128 {
+        let (start, end) = (*self.values.start(), *self.values.end());
+        if end < start {
+            0
+        } else {
+            (end - start).saturating_add(1)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Outcome of dry-running `create_room` with one value of the swept argument.
+pub struct SweepResult {
+    pub value: u128,
+    /// `None` if the call would succeed, otherwise the abort or execution error.
+    pub error: Option<String>,
+    /// Net gas cost in MIST.
+    pub gas: i64,
+}
+
+/// Dry-run `create_room` once for every value of `range`, passing the value as the pure
+/// argument that follows the room and the cards.
+///
+/// The type of that argument is read from the function's signature, so `create_room` must
+/// take an integer parameter there. Nothing is signed or executed. Ranges of more than
+/// `max_values` values are rejected before anything is fetched. Every value is dry-run with
+/// the configured budget, whatever the estimate basis of the client.
+pub async fn sweep_create_room(
+    client: &GameClient,
+    card: ObjectID,
+    range: &ArgRange,
+    max_values: u128,
+) -> Result<Vec<SweepResult>, anyhow::Error> {
+    if range.len() > max_values {
+        bail!(
+            "`{}` takes {} values, more than the limit of {max_values} dry runs",
+            range.name,
+            range.len()
+        );
+    }
+    let function = client
+        .modules()
+        .function(client.deployment().package, "gamecards", "create_room")
+        .await?;
+    let plan = client
+        .plan_without_estimate(&Action::CreateRoom {
+            card,
+            extra_objects: vec![],
+            room_owner: None,
+        })
+        .await?;
+    let (call, position) = plan
+        .commands
+        .iter()
+        .enumerate()
+        .find_map(|(i, command)| match command {
+            Command::MoveCall(call) if call.function.as_str() == "create_room" => {
+                Some((i, call.arguments.len()))
+            }
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("The plan does not call create_room"))?;
+    let parameter = function.parameters.get(position).ok_or_else(|| {
+        anyhow!(
+            "create_room takes no argument after the room and the cards to use for `{}`",
+            range.name
+        )
+    })?;
+
+    let mut results = vec![];
+    for value in range.values.clone() {
+        let mut plan = plan.clone();
+        let input = u16::try_from(plan.inputs.len())?;
+        plan.inputs.push(
+            pure_integer(parameter, value)
+                .with_context(|| format!("Cannot pass {value} as `{}`", range.name))?,
+        );
+        if let Command::MoveCall(move_call) = &mut plan.commands[call] {
+            move_call.arguments.push(Argument::Input(input));
+        }
+
+        let estimate = client.dry_run_plan(&plan).await?;
+        results.push(SweepResult {
+            value,
+            gas: estimate.net_cost(),
+            error: estimate.error,
+        });
+    }
+    Ok(results)
+}
+
+/// Encode an integer as a pure argument of the given Move type.
+fn pure_integer(parameter: &SuiMoveNormalizedType, value: u128) -> Result<CallArg, anyhow::Error> {
+    let type_tag = match parameter {
+        SuiMoveNormalizedType::U8 => TypeTag::U8,
+        SuiMoveNormalizedType::U16 => TypeTag::U16,
+        SuiMoveNormalizedType::U32 => TypeTag::U32,
+        SuiMoveNormalizedType::U64 => TypeTag::U64,
+        SuiMoveNormalizedType::U128 => TypeTag::U128,
+        other => bail!("The parameter has type {other:?}, not an integer type"),
+    };
+    let bytes = pure::encode(&type_tag, &serde_json::json!(value.to_string()))?;
+    Ok(CallArg::Pure(bytes))
+}
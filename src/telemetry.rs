@@ -0,0 +1,97 @@
+//! Export of the tracing spans of the library, enabled by the `tracing-export` feature.
+//!
+//! Fetching objects, building, signing and executing transactions each run in their own
+//! span, and [`crate::GameClient::execute`] wraps every transaction in a `transaction`
+//! span tagged with its digest. Without a subscriber these spans cost next to nothing;
+//! [`init`] installs one that writes them out.
+
+use std::{fs::File, path::Path, sync::Mutex};
+
+use opentelemetry::{trace::TracerProvider as _, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace, Resource};
+use tracing_subscriber::{
+    fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt, Layer,
+};
+
+/// Name the spans are exported under.
+const SERVICE_NAME: &str = "sui-connect";
+
+/// Where spans are exported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceTarget {
+    /// One JSON object per line, written when each span closes with its busy and idle time.
+    File(String),
+    /// An OTLP collector over gRPC. Without an endpoint, the standard
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT` variable applies, falling back to `localhost:4317`.
+    Otlp { endpoint: Option<String> },
+}
+
+impl std::str::FromStr for TraceTarget {
+    type Err = std::convert::Infallible;
+
+    /// `otlp`, an `http://` or `https://` collector URL, or else a file path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(if s == "otlp" {
+            TraceTarget::Otlp { endpoint: None }
+        } else if s.starts_with("http://") || s.starts_with("https://") {
+            TraceTarget::Otlp {
+                endpoint: Some(s.to_string()),
+            }
+        } else {
+            TraceTarget::File(s.to_string())
+        })
+    }
+}
+
+/// Flushes exported spans when dropped; keep it alive until the program exits.
+pub struct TraceGuard {
+    otlp: bool,
+}
+
+impl Drop for TraceGuard {
+    fn drop(&mut self) {
+        if self.otlp {
+            opentelemetry::global::shutdown_tracer_provider();
+        }
+    }
+}
+
+/// Install a global subscriber that exports the spans of this crate to `target`.
+///
+/// OTLP export batches spans on the Tokio runtime, so this must be called from within one.
+pub fn init(target: &TraceTarget) -> Result<TraceGuard, anyhow::Error> {
+    let filter =
+        tracing_subscriber::filter::Targets::new().with_target("sui_connect", tracing::Level::INFO);
+    match target {
+        TraceTarget::File(path) => {
+            let file = File::create(Path::new(path))?;
+            let layer = tracing_subscriber::fmt::layer()
+                .json()
+                .with_span_events(FmtSpan::CLOSE)
+                .with_writer(Mutex::new(file));
+            tracing_subscriber::registry()
+                .with(layer.with_filter(filter))
+                .try_init()?;
+            Ok(TraceGuard { otlp: false })
+        }
+        TraceTarget::Otlp { endpoint } => {
+            let mut exporter = opentelemetry_otlp::new_exporter().tonic();
+            if let Some(endpoint) = endpoint {
+                exporter = exporter.with_endpoint(endpoint);
+            }
+            let resource = Resource::new([KeyValue::new("service.name", SERVICE_NAME)]);
+            let provider = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(exporter)
+                .with_trace_config(trace::Config::default().with_resource(resource))
+                .install_batch(runtime::Tokio)?;
+            opentelemetry::global::set_tracer_provider(provider.clone());
+            let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer(SERVICE_NAME));
+            tracing_subscriber::registry()
+                .with(layer.with_filter(filter))
+                .try_init()?;
+            Ok(TraceGuard { otlp: true })
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    fs::File,
+    future::Future,
+    io::Write,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::warn;
+
+/// Keys whose values are replaced by `"<redacted>"` wherever they appear in an entry.
+/// Signatures are left out even though they are public once submitted, so that a transcript
+/// of a transaction that never landed cannot be used to submit it.
+const REDACTED_KEYS: &[&str] = &[
+    "txSignatures",
+    "signatures",
+    "signature",
+    "rawTransaction",
+    "privateKey",
+    "mnemonic",
+];
+
+tokio::task_local! {
+    /// The transcript of the client call that is running, see [`Transcript::scope`].
+    static CURRENT: Transcript;
+}
+
+/// A JSON lines record of the RPC calls a [`crate::GameClient`] makes and of the decisions
+/// it takes locally, such as the gas coin it selects and the budget it computes.
+///
+/// The calls made by the helper functions a client uses, such as the objects, coins, modules
+/// and gas price a plan fetches, are recorded too, and so are the calls of any code running
+/// in a [`Transcript::scope`], see [`record_rpc`].
+///
+/// Each line is one entry with a sequence number, a timestamp in milliseconds and its
+/// `kind`: `rpc` for a call with its `method`, `params` and `response` or `error`, and
+/// `decision` for a local choice with its `name` and `details`. Entries are written as they
+/// happen, so a transcript is complete up to a crash. Clones write to the same file.
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    writer: Arc<Mutex<Writer>>,
+}
+
+#[derive(Debug)]
+struct Writer {
+    file: File,
+    sequence: u64,
+}
+
+impl Transcript {
+    /// Start a transcript in a new file, replacing any file at `path`.
+    pub fn create(path: &Path) -> Result<Self, anyhow::Error> {
+        let file = File::create(path)
+            .with_context(|| format!("Cannot create the transcript {}", path.display()))?;
+        Ok(Self {
+            writer: Arc::new(Mutex::new(Writer { file, sequence: 0 })),
+        })
+    }
+
+    /// Record an RPC call with its parameters and its response or error.
+    pub fn rpc<T: Serialize>(
+        &self,
+        method: &str,
+        params: Value,
+        result: Result<T, &anyhow::Error>,
+    ) {
+        let mut entry = json!({ "kind": "rpc", "method": method, "params": params });
+        match result.map(serde_json::to_value) {
+            Ok(Ok(response)) => entry["response"] = response,
+            Ok(Err(e)) => entry["error"] = format!("Cannot serialize the response: {e}").into(),
+            Err(e) => entry["error"] = format!("{e:#}").into(),
+        }
+        self.write(entry);
+    }
+
+    /// Run `future` with `transcript`, if any, as the current transcript, so that the helper
+    /// functions it calls record their RPCs to it with [`record_rpc`].
+    pub async fn scope<F: Future>(transcript: Option<&Transcript>, future: F) -> F::Output {
+        match transcript {
+            Some(transcript) => CURRENT.scope(transcript.clone(), future).await,
+            None => future.await,
+        }
+    }
+
+    /// Record a decision taken locally.
+    pub fn decision(&self, name: &str, details: Value) {
+        self.write(json!({ "kind": "decision", "name": name, "details": details }));
+    }
+
+    fn write(&self, mut entry: Value) {
+        redact(&mut entry);
+        let mut writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        writer.sequence += 1;
+        let time_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis() as u64);
+        entry["sequence"] = writer.sequence.into();
+        entry["time_ms"] = time_ms.into();
+        if let Err(e) = writeln!(writer.file, "{entry}") {
+            warn!("Cannot write to the transcript ({e})");
+        }
+    }
+}
+
+/// Record an RPC call to the current transcript, if a [`Transcript::scope`] is running.
+/// Helper functions that take a bare `SuiClient` use this, since they have no transcript of
+/// their own.
+pub fn record_rpc<T: Serialize>(
+    method: &str,
+    params: impl FnOnce() -> Value,
+    result: Result<T, &anyhow::Error>,
+) {
+    // Outside of a scope there is nothing to record to
+    let _ = CURRENT.try_with(|transcript| transcript.rpc(method, params(), result));
+}
+
+/// Replace the value of every key in [`REDACTED_KEYS`], at any depth, by `"<redacted>"`.
+pub fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if REDACTED_KEYS.contains(&key.as_str()) {
+                    *value = "<redacted>".into();
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// An RPC URL without the credentials or query string that often carry API keys.
+pub fn redact_url(url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    match authority.rsplit_once('@') {
+        Some((_, host)) => format!("{scheme}://<redacted>@{host}{path}"),
+        None => url.to_string(),
+    }
+}
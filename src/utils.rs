@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 // use std::{str::FromStr, time::Duration};
+use std::time::{Duration, Instant};
 
-// use anyhow::bail;
+use anyhow::{anyhow, bail};
 use sui_config::{
     sui_config_dir, Config, PersistedConfig, SUI_CLIENT_CONFIG, SUI_KEYSTORE_FILENAME,
 };
+use sui_json_rpc_types::Balance;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
 use sui_sdk::{
     sui_client_config::{SuiClientConfig, SuiEnv},
@@ -15,54 +17,317 @@ use sui_sdk::{
 use tracing::info;
 
 use sui_sdk::types::{
-    base_types::SuiAddress,
-    crypto::SignatureScheme::ED25519,
+    base_types::{ObjectID, SuiAddress},
+    crypto::SignatureScheme::{self, ED25519},
+};
+use sui_sdk::{
+    SuiClient, SuiClientBuilder, SUI_DEVNET_URL, SUI_LOCAL_NETWORK_URL, SUI_TESTNET_URL,
 };
-use sui_sdk::{SuiClient, SuiClientBuilder};
-
 
+use crate::transcript;
 
 // if you use the sui-test-validator and use the local network; if it does not work, try with port 5003.
-// const SUI_FAUCET: &str = "http://127.0.0.1:9123/gas";
+const SUI_LOCAL_FAUCET: &str = "http://127.0.0.1:9123/gas";
+const SUI_DEVNET_FAUCET: &str = "https://faucet.devnet.sui.io/v1/gas";
+const SUI_TESTNET_FAUCET: &str = "https://faucet.testnet.sui.io/v1/gas";
+
+/// How long to wait for faucet coins to show up in the recipient's balance.
+const FAUCET_TIMEOUT: Duration = Duration::from_secs(60);
+
+const SUI_MAINNET_URL: &str = "https://fullnode.mainnet.sui.io:443";
+
+/// The Sui network to connect to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    Mainnet,
+    #[default]
+    Testnet,
+    Devnet,
+    Localnet,
+}
+
+impl Network {
+    /// The JSON-RPC URL of the public full node of this network.
+    pub fn rpc_url(self) -> &'static str {
+        match self {
+            Network::Mainnet => SUI_MAINNET_URL,
+            Network::Testnet => SUI_TESTNET_URL,
+            Network::Devnet => SUI_DEVNET_URL,
+            Network::Localnet => SUI_LOCAL_NETWORK_URL,
+        }
+    }
+
+    /// The faucet that hands out SUI on this network, if there is one.
+    pub fn faucet_url(self) -> Option<&'static str> {
+        match self {
+            Network::Mainnet => None,
+            Network::Testnet => Some(SUI_TESTNET_FAUCET),
+            Network::Devnet => Some(SUI_DEVNET_FAUCET),
+            Network::Localnet => Some(SUI_LOCAL_FAUCET),
+        }
+    }
+
+    /// Link to an object on the Suiscan explorer, if this network has a public explorer.
+    pub fn explorer_object_url(self, id: ObjectID) -> Option<String> {
+        let network = match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+            Network::Localnet => return None,
+        };
+        Some(format!("https://suiscan.xyz/{network}/object/{id}"))
+    }
+
+    /// Percentage added on top of the reference gas price when none is given.
+    ///
+    /// Mainnet gets a small margin so that a transaction still lands if the reference
+    /// price ticks up between fetching it and submitting the transaction.
+    pub fn default_gas_price_margin_pct(self) -> u64 {
+        match self {
+            Network::Mainnet => 5,
+            Network::Testnet | Network::Devnet | Network::Localnet => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+            Network::Localnet => "localnet",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The WebSocket URL of the full node at `rpc_url`, which serves subscriptions on the same
+/// host and port: `http` becomes `ws` and `https` becomes `wss`.
+pub fn ws_url(rpc_url: &str) -> String {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{rest}")
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        format!("ws://{rest}")
+    } else {
+        rpc_url.to_string()
+    }
+}
+
+/// Build a sui client connected to the full node at `rpc_url` that can also subscribe to
+/// events, over the WebSocket URL derived from it with `ws_url`.
+pub async fn ws_client_at(rpc_url: &str) -> Result<SuiClient, anyhow::Error> {
+    Ok(SuiClientBuilder::default()
+        .ws_url(ws_url(rpc_url))
+        .build(rpc_url)
+        .await?)
+}
 
 /// Return a sui client to interact with the APIs,
 /// the active address of the local wallet, and another address that can be used as a recipient.
 ///
-/// By default, this function will set up a wallet locally if there isn't any, or reuse the
-/// existing one and its active address. This function should be used when two addresses are needed,
+/// The wallet is only read, so it has to exist and hold a second address; `retrieve_wallet`
+/// sets one up with two addresses. This function should be used when two addresses are needed,
 /// e.g., transferring objects from one address to another.
-pub async fn setup_for_write() -> Result<(SuiClient, SuiAddress, SuiAddress), anyhow::Error> {
-    let (client, active_address) = setup_for_read().await?;
-    let wallet = retrieve_wallet()?;
-    let addresses = wallet.get_addresses();
-    let addresses = addresses
-        .into_iter()
-        .filter(|address| address != &active_address)
-        .collect::<Vec<_>>();
-    let recipient = addresses
-        .first()
-        .expect("Cannot get the recipient address needed for writing operations. Aborting");
-
-    Ok((client, active_address, *recipient))
-}
-
-/// Return a sui client to interact with the APIs and an active address from the local wallet.
-///
-/// This function sets up a wallet in case there is no wallet locally,
-/// and ensures that the active address of the wallet has SUI on it.
-/// If there is no SUI owned by the active address, then it will request
-/// SUI from the faucet.
-pub async fn setup_for_read() -> Result<(SuiClient, SuiAddress), anyhow::Error> {
-    let client = SuiClientBuilder::default().build_testnet().await?;
-    println!("Sui testnet version is: {}", client.api_version());
-    let mut wallet = retrieve_wallet()?;
-    assert!(wallet.get_addresses().len() >= 2);
-    let active_address = wallet.active_address()?;
+pub async fn setup_for_write(
+    network: Network,
+) -> Result<(SuiClient, SuiAddress, SuiAddress), anyhow::Error> {
+    setup_for_write_at(network, network.rpc_url()).await
+}
+
+/// Like `setup_for_write`, but connects to the full node at `rpc_url`.
+pub async fn setup_for_write_at(
+    network: Network,
+    rpc_url: &str,
+) -> Result<(SuiClient, SuiAddress, SuiAddress), anyhow::Error> {
+    let (client, active_address) = setup_sender_at(network, rpc_url).await?;
+    let keystore = FileBasedKeystore::new(&sui_config_dir()?.join(SUI_KEYSTORE_FILENAME))?;
+    let recipient = other_address(active_address, &keystore.addresses())?;
 
+    Ok((client, active_address, recipient))
+}
+
+/// The first of `addresses` that is not `sender`, to receive what `sender` sends.
+pub fn other_address(
+    sender: SuiAddress,
+    addresses: &[SuiAddress],
+) -> Result<SuiAddress, anyhow::Error> {
+    addresses
+        .iter()
+        .copied()
+        .find(|address| address != &sender)
+        .ok_or_else(|| {
+            anyhow!(
+                "The wallet only holds {sender}, and a second address is needed as the \
+                 recipient; create one with `sui client new-address ed25519`"
+            )
+        })
+}
+
+/// Return a sui client and the active address of the local wallet, for writing operations
+/// that do not need a recipient. Unlike `setup_for_write`, this works with a wallet holding
+/// a single address.
+///
+/// Like `address_or_active`, the wallet is only read: no key is generated and the active
+/// address is left as it is.
+pub async fn setup_sender_at(
+    network: Network,
+    rpc_url: &str,
+) -> Result<(SuiClient, SuiAddress), anyhow::Error> {
+    let active_address = address_or_active(None).map_err(|e| {
+        e.context("No sender is available; set up a wallet with `sui client` or `retrieve_wallet`")
+    })?;
+    let client = setup_for_read_at(network, rpc_url).await?;
     println!("Wallet active address is: {active_address}");
+
     Ok((client, active_address))
 }
 
+/// Return a sui client to interact with the read APIs.
+///
+/// Unlike `setup_for_write`, this function never touches the local wallet, so it works on
+/// machines without a keystore.
+pub async fn setup_for_read(network: Network) -> Result<SuiClient, anyhow::Error> {
+    setup_for_read_at(network, network.rpc_url()).await
+}
+
+/// Like `setup_for_read`, but connects to the full node at `rpc_url`, e.g. a private
+/// RPC provider, instead of the network's public full node.
+pub async fn setup_for_read_at(
+    network: Network,
+    rpc_url: &str,
+) -> Result<SuiClient, anyhow::Error> {
+    let client = SuiClientBuilder::default().build(rpc_url).await?;
+    eprintln!("Sui {network} version is: {}", client.api_version());
+    Ok(client)
+}
+
+/// Return `address`, or the active address of the local wallet if none is given.
+///
+/// The wallet configuration is only read, never created, so this fails with a clear
+/// message instead of generating keys when no wallet exists.
+pub fn address_or_active(address: Option<SuiAddress>) -> Result<SuiAddress, anyhow::Error> {
+    if let Some(address) = address {
+        return Ok(address);
+    }
+    let wallet_conf = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
+    if !wallet_conf.exists() {
+        bail!("No wallet is configured, pass an address explicitly");
+    }
+    let client_config: SuiClientConfig = PersistedConfig::read(&wallet_conf)?;
+    client_config
+        .active_address
+        .ok_or_else(|| anyhow!("The wallet has no active address, pass an address explicitly"))
+}
+
+/// Request SUI for `address` from the faucet of the network and wait until the coins
+/// show up in its balance.
+pub async fn request_tokens_from_faucet(
+    sui: &SuiClient,
+    network: Network,
+    address: SuiAddress,
+) -> Result<(), anyhow::Error> {
+    let faucet_url = network
+        .faucet_url()
+        .ok_or_else(|| anyhow!("There is no faucet on {network}"))?;
+    let initial_balance = balance(sui, address).await?.total_balance;
+
+    let body = serde_json::json!({ "FixedAmountRequest": { "recipient": address.to_string() } });
+    let response = reqwest::Client::new()
+        .post(faucet_url)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .map_err(anyhow::Error::from);
+    transcript::record_rpc(
+        "faucet",
+        || serde_json::json!({ "url": faucet_url, "request": body }),
+        response.as_ref().map(|response| response.status().as_u16()),
+    );
+    let response = response?;
+    if !response.status().is_success() {
+        bail!(
+            "The faucet rejected the request ({}): {}",
+            response.status(),
+            response.text().await?
+        );
+    }
+    info!("Faucet request for {address} accepted");
+
+    let start = Instant::now();
+    loop {
+        let balance = balance(sui, address).await?;
+        if balance.total_balance > initial_balance {
+            return Ok(());
+        }
+        if start.elapsed() >= FAUCET_TIMEOUT {
+            bail!(
+                "Faucet coins did not arrive at {address} within {}s",
+                FAUCET_TIMEOUT.as_secs()
+            );
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
+/// Total SUI balance of an address.
+pub async fn balance(sui: &SuiClient, address: SuiAddress) -> Result<Balance, anyhow::Error> {
+    let result = sui
+        .coin_read_api()
+        .get_balance(address, None)
+        .await
+        .map_err(anyhow::Error::from);
+    transcript::record_rpc(
+        "suix_getBalance",
+        || serde_json::json!({ "owner": address }),
+        result.as_ref(),
+    );
+    result
+}
+
+/// The address of a key in the local keystore, given as a `0x` address or a key alias.
+///
+/// Fails if the keystore has no such key, so a misconfigured sender is caught before any
+/// transaction is built.
+pub fn keystore_address(sender: &str) -> Result<SuiAddress, anyhow::Error> {
+    let keystore_path = sui_config_dir()?.join(SUI_KEYSTORE_FILENAME);
+    if !keystore_path.exists() {
+        bail!("There is no keystore at {}", keystore_path.display());
+    }
+    let keystore = FileBasedKeystore::new(&keystore_path)?;
+    if sender.starts_with("0x") {
+        let address: SuiAddress = sender.parse()?;
+        if !keystore.addresses().contains(&address) {
+            bail!("The keystore has no key for {address}");
+        }
+        return Ok(address);
+    }
+    keystore
+        .get_address_by_alias(sender.to_string())
+        .copied()
+        .map_err(|_| anyhow!("The keystore has no key with the alias `{sender}`"))
+}
+
+/// Generate a new key of the given scheme in the local keystore and return its address
+/// and recovery phrase.
+pub fn generate_account(
+    scheme: SignatureScheme,
+    alias: Option<String>,
+) -> Result<(SuiAddress, String), anyhow::Error> {
+    let keystore_path = sui_config_dir()?.join(SUI_KEYSTORE_FILENAME);
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    let (address, phrase, _) = keystore.generate_and_add_new_key(scheme, alias, None, None)?;
+    Ok((address, phrase))
+}
+
+/// Set up a local wallet with at least two addresses, creating the keystore and the client
+/// config if there are none, and return it.
+///
+/// The first address becomes the active one unless the wallet already has an active address.
+/// Nothing else in this module generates keys, so this is the way to get a wallet that
+/// `setup_for_write` can use.
 pub fn retrieve_wallet() -> Result<WalletContext, anyhow::Error> {
     let wallet_conf = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
     let keystore_path = sui_config_dir()?.join(SUI_KEYSTORE_FILENAME);
@@ -104,10 +369,12 @@ pub fn retrieve_wallet() -> Result<WalletContext, anyhow::Error> {
         keystore.generate_and_add_new_key(ED25519, None, None, None)?;
     }
 
-    client_config.active_address = Some(default_active_address);
-    client_config.save(&wallet_conf)?;
+    if client_config.active_address.is_none() {
+        client_config.active_address = Some(default_active_address);
+        client_config.save(&wallet_conf)?;
+    }
 
     let wallet = WalletContext::new(&wallet_conf, Some(std::time::Duration::from_secs(60)), None)?;
 
     Ok(wallet)
-}
\ No newline at end of file
+}
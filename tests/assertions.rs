@@ -0,0 +1,64 @@
+//! Post-transaction assertions checked against a hand-built response.
+
+use sui_connect::assertions::{Assertions, CreatedType, ExpectedStatus};
+use sui_json_rpc_types::{ObjectChange, SuiTransactionBlockResponse};
+use sui_sdk::types::{
+    base_types::{ObjectID, SequenceNumber, SuiAddress},
+    digests::{ObjectDigest, TransactionDigest},
+    object::Owner,
+    parse_sui_struct_tag,
+};
+
+const ROOM: &str = "0x7::gamecards::Room";
+
+fn response_creating_rooms(count: u8) -> Result<SuiTransactionBlockResponse, anyhow::Error> {
+    let mut response = SuiTransactionBlockResponse::new(TransactionDigest::default());
+    let changes = (0..count)
+        .map(|i| -> Result<_, anyhow::Error> {
+            Ok(ObjectChange::Created {
+                sender: SuiAddress::ZERO,
+                owner: Owner::Shared {
+                    initial_shared_version: SequenceNumber::from_u64(1),
+                },
+                object_type: parse_sui_struct_tag(ROOM)?,
+                object_id: ObjectID::from_single_byte(i),
+                version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::random(),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    response.object_changes = Some(changes);
+    Ok(response)
+}
+
+#[test]
+fn created_types_parse_with_an_optional_count() -> Result<(), anyhow::Error> {
+    let any: CreatedType = ROOM.parse()?;
+    assert_eq!(any.object_type, parse_sui_struct_tag(ROOM)?);
+    assert_eq!(any.count, None);
+
+    let exact: CreatedType = format!("{ROOM}=1").parse()?;
+    assert_eq!(exact.count, Some(1));
+
+    assert!(format!("{ROOM}=one").parse::<CreatedType>().is_err());
+    Ok(())
+}
+
+#[test]
+fn every_unmet_assertion_is_reported() -> Result<(), anyhow::Error> {
+    let one_room = Assertions {
+        status: None,
+        created: vec![format!("{ROOM}=1").parse()?],
+    };
+    assert!(one_room.check(&response_creating_rooms(1)?).is_ok());
+    assert!(one_room.check(&response_creating_rooms(2)?).is_err());
+
+    let combined = Assertions {
+        // The hand-built response has no effects, so it cannot meet a status assertion
+        status: Some(ExpectedStatus::Success),
+        created: vec![ROOM.parse()?, "0x7::gamecards::Card".parse()?],
+    };
+    let failures = combined.failures(&response_creating_rooms(1)?);
+    assert_eq!(failures.len(), 2, "{failures:?}");
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! Helpers for the integration tests, which run against a local network started with
+//! `sui start --with-faucet --force-regenesis` and the `sui` CLI configured for it.
+
+// Each test crate that includes this module only uses some of its helpers
+#![allow(dead_code)]
+
+use std::{env, path::Path, process::Command, str::FromStr};
+
+use anyhow::{anyhow, bail, Context};
+use sui_connect::{
+    game,
+    gas::{GasSettings, DEFAULT_GAS_BUDGET},
+    GameClient, TransactionPlan,
+};
+use sui_json_rpc_types::ObjectChange;
+use sui_sdk::types::{
+    base_types::ObjectID,
+    object::Owner,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{Command, ProgrammableMoveCall},
+    Identifier,
+};
+
+/// Gas settings of the transactions sent by the tests.
+pub const GAS: GasSettings = GasSettings {
+    budget: DEFAULT_GAS_BUDGET,
+    price_margin_pct: 0,
+    estimate_basis: None,
+};
+
+/// A `gamecards` package on the local network and the shared game room it created.
+pub struct PublishedPackage {
+    pub package: ObjectID,
+    pub room: ObjectID,
+}
+
+/// Publish the Move package at `path` with the `sui` CLI and return its package ID and the
+/// shared object created by its `init` function.
+pub fn publish_package(path: &Path) -> Result<PublishedPackage, anyhow::Error> {
+    let output = Command::new("sui")
+        .args(["client", "publish", "--json", "--gas-budget", "500000000"])
+        .arg(path)
+        .output()
+        .context("Cannot run the `sui` CLI")?;
+    if !output.status.success() {
+        bail!(
+            "Publishing {} failed: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let response: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let changes = response["objectChanges"]
+        .as_array()
+        .ok_or_else(|| anyhow!("The publish output has no object changes"))?;
+
+    let package = changes
+        .iter()
+        .find(|change| change["type"] == "published")
+        .and_then(|change| change["packageId"].as_str())
+        .ok_or_else(|| anyhow!("The publish output has no published package"))?;
+    let room = changes
+        .iter()
+        .find(|change| change["type"] == "created" && change["owner"].get("Shared").is_some())
+        .and_then(|change| change["objectId"].as_str())
+        .ok_or_else(|| anyhow!("Publishing did not create a shared game room"))?;
+
+    Ok(PublishedPackage {
+        package: ObjectID::from_str(package)?,
+        room: ObjectID::from_str(room)?,
+    })
+}
+
+/// The package to test against: published from `GAMECARDS_PACKAGE_PATH`, or already
+/// deployed at `GAMECARDS_PACKAGE_ID` with its room at `GAMECARDS_ROOM_ID`.
+pub fn gamecards_package() -> Result<PublishedPackage, anyhow::Error> {
+    if let Ok(path) = env::var("GAMECARDS_PACKAGE_PATH") {
+        return publish_package(Path::new(&path));
+    }
+    let package = env::var("GAMECARDS_PACKAGE_ID")
+        .context("Set GAMECARDS_PACKAGE_PATH or GAMECARDS_PACKAGE_ID")?;
+    let room = env::var("GAMECARDS_ROOM_ID").context("Set GAMECARDS_ROOM_ID")?;
+    Ok(PublishedPackage {
+        package: ObjectID::from_str(&package)?,
+        room: ObjectID::from_str(&room)?,
+    })
+}
+
+/// Mint a card for the sender by calling `gamecards::<GAMECARDS_MINT_FUNCTION>` (default
+/// `mint_card`) and return its ID, or take it from `GAMECARDS_CARD_ID`.
+pub async fn mint_card(client: &GameClient) -> Result<ObjectID, anyhow::Error> {
+    if let Ok(card) = env::var("GAMECARDS_CARD_ID") {
+        return Ok(ObjectID::from_str(&card)?);
+    }
+    let function = env::var("GAMECARDS_MINT_FUNCTION").unwrap_or_else(|_| "mint_card".into());
+
+    let sender = client.sender().unwrap();
+    let mut ptb = ProgrammableTransactionBuilder::new();
+    let card = ptb.command(Command::MoveCall(Box::new(ProgrammableMoveCall {
+        package: client.deployment().package,
+        module: Identifier::new("gamecards")?,
+        function: Identifier::new(function)?,
+        type_arguments: vec![],
+        arguments: vec![],
+    })));
+    ptb.transfer_args(sender, vec![card]);
+
+    let gas_coin = game::gas_coin(client.sui(), sender, GAS.budget, None).await?;
+    let gas_price = client.sui().read_api().get_reference_gas_price().await?;
+    let plan = TransactionPlan::new(sender, ptb.finish(), gas_coin, GAS.budget, gas_price);
+    let response = client.execute_plan(plan).await?;
+
+    let changes = response.object_changes.unwrap_or_default();
+    changes
+        .into_iter()
+        .find_map(|change| match change {
+            ObjectChange::Created {
+                object_id, owner, ..
+            } if owner == Owner::AddressOwner(sender) => Some(object_id),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("Minting did not create a card for the sender"))
+}
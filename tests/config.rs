@@ -0,0 +1,134 @@
+//! Validation of config files.
+
+use sui_connect::{
+    config::Config,
+    retry::{CongestionPolicy, RetryPolicy},
+};
+use sui_sdk::types::base_types::ObjectID;
+
+#[test]
+fn every_invalid_key_is_reported() {
+    let problems = Config::problems(
+        r#"
+        network = "moonnet"
+        package = "not an id"
+        room = "0x7"
+        colour = "blue"
+
+        [rpc.read]
+        attempts = "many"
+        timeout_secs = 10
+        backoff_ms = 200
+        "#,
+    );
+
+    let keys: Vec<_> = problems
+        .iter()
+        .map(|problem| problem.split(':').next().unwrap())
+        .collect();
+    assert_eq!(keys, ["colour", "network", "package", "rpc.read"]);
+}
+
+#[test]
+fn the_valid_keys_are_kept_despite_invalid_ones() -> Result<(), anyhow::Error> {
+    let check = Config::check(
+        r#"
+        package = "not an id"
+        room = "0x7"
+        default_sender = "alice"
+
+        [rpc.read]
+        attempts = "many"
+
+        [rpc.write]
+        attempts = 7
+        "#,
+    );
+
+    assert_eq!(check.invalid_keys, ["package"]);
+    assert_eq!(check.problems.len(), 2);
+    let config = check.config.expect("the file is valid TOML");
+    assert_eq!(config.package, None);
+    assert_eq!(config.room, Some(ObjectID::from_hex_literal("0x7")?));
+    assert_eq!(config.default_sender.as_deref(), Some("alice"));
+    assert_eq!(config.rpc.read, RetryPolicy::READ);
+    assert_eq!(config.rpc.write.attempts, 7);
+    Ok(())
+}
+
+#[test]
+fn a_file_that_is_not_toml_has_no_settings() {
+    let check = Config::check("network = ");
+    assert!(check.config.is_none());
+    assert_eq!(check.problems.len(), 1);
+}
+
+#[test]
+fn a_valid_config_has_no_problems() {
+    let problems = Config::problems(
+        r#"
+        network = "localnet"
+        room = "0x7"
+        card_type = "0x7::gamecards::Card"
+        "#,
+    );
+    assert!(problems.is_empty(), "{problems:?}");
+}
+
+#[test]
+fn a_partial_policy_keeps_the_class_defaults() -> Result<(), anyhow::Error> {
+    let config: Config = toml::from_str(
+        r#"
+        [rpc.read]
+        attempts = 5
+        "#,
+    )?;
+
+    assert_eq!(config.rpc.read.attempts, 5);
+    assert_eq!(config.rpc.read.timeout_secs, RetryPolicy::READ.timeout_secs);
+    assert_eq!(config.rpc.read.backoff_ms, RetryPolicy::READ.backoff_ms);
+    assert_eq!(config.rpc.write, RetryPolicy::WRITE);
+    Ok(())
+}
+
+#[test]
+fn the_congestion_policy_has_no_timeout() -> Result<(), anyhow::Error> {
+    let config: Config = toml::from_str(
+        r#"
+        [rpc.congestion]
+        attempts = 6
+        "#,
+    )?;
+    assert_eq!(config.rpc.congestion.attempts, 6);
+    assert_eq!(
+        config.rpc.congestion.backoff_ms,
+        CongestionPolicy::DEFAULT.backoff_ms
+    );
+
+    let problems = Config::problems(
+        r#"
+        [rpc.congestion]
+        timeout_secs = 120
+        "#,
+    );
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].starts_with("rpc.congestion: "));
+    Ok(())
+}
+
+#[test]
+fn zero_attempts_are_a_problem() {
+    let problems = Config::problems(
+        r#"
+        [rpc.read]
+        attempts = 0
+
+        [rpc.congestion]
+        attempts = 0
+        "#,
+    );
+    assert_eq!(problems.len(), 2);
+    for policy in ["rpc.read: ", "rpc.congestion: "] {
+        assert!(problems.iter().any(|problem| problem.starts_with(policy)));
+    }
+}
@@ -0,0 +1,68 @@
+//! Confirmation of transactions whose submission timed out, with a simulated full node.
+
+use std::{cell::Cell, time::Duration};
+
+use sui_connect::game::{self, confirm_submission};
+use sui_json_rpc_types::SuiTransactionBlockResponse;
+use sui_sdk::{error::Error, types::digests::TransactionDigest};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+#[tokio::test]
+async fn a_local_execution_timeout_is_confirmed_by_digest() -> Result<(), anyhow::Error> {
+    let digest = TransactionDigest::random();
+    let lookups = Cell::new(0);
+    let response = confirm_submission(
+        Err(Error::FailToConfirmTransactionStatus(digest, 60)),
+        digest,
+        Duration::from_secs(5),
+        POLL_INTERVAL,
+        || {
+            lookups.set(lookups.get() + 1);
+            // The full node only knows the transaction from the second lookup on
+            let found = lookups.get() > 1;
+            async move {
+                match found {
+                    true => Ok(SuiTransactionBlockResponse::new(digest)),
+                    false => Err(anyhow::anyhow!("Could not find the referenced transaction")),
+                }
+            }
+        },
+    )
+    .await?;
+
+    assert_eq!(response.digest, digest);
+    assert_eq!(lookups.get(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn other_timeouts_are_not_looked_up() {
+    let digest = TransactionDigest::random();
+    let lookups = Cell::new(0);
+    let result = confirm_submission(
+        Err(Error::DataError("connect timeout".to_string())),
+        digest,
+        Duration::from_secs(5),
+        POLL_INTERVAL,
+        || {
+            lookups.set(lookups.get() + 1);
+            async move { Ok(SuiTransactionBlockResponse::new(digest)) }
+        },
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(lookups.get(), 0);
+}
+
+#[test]
+fn only_unconfirmed_submissions_may_have_landed() {
+    let digest = TransactionDigest::random();
+    assert!(game::is_unconfirmed_submission(
+        &Error::FailToConfirmTransactionStatus(digest, 60)
+    ));
+    assert!(!game::is_unconfirmed_submission(&Error::DataError(
+        "request timed out".to_string()
+    )));
+}
@@ -0,0 +1,28 @@
+//! Classification of shared-object congestion errors.
+
+use sui_connect::retry::is_congestion_error;
+
+#[test]
+fn a_cancelled_congested_transaction_is_retryable() {
+    // The execution status of a transaction cancelled by the validators, as rendered in a
+    // JSON-RPC response
+    let status = "ExecutionCancelledDueToSharedObjectCongestion { congested_objects: \
+                  CongestedObjects([0x0000000000000000000000000000000000000000000000000000000000000007]) }";
+    assert!(is_congestion_error(status));
+}
+
+#[test]
+fn a_rejected_submission_with_pending_transactions_is_retryable() {
+    let error = "Transaction execution failed: TooManyTransactionsPendingOnObject { \
+                 object_id: 0x7, queue_len: 1000, threshold: 1000 }";
+    assert!(is_congestion_error(error));
+}
+
+#[test]
+fn other_failures_are_not_retryable() {
+    assert!(!is_congestion_error(
+        "MoveAbort(MoveLocation { module: ModuleId { address: 0x7, name: Identifier(\"gamecards\") }, \
+         function: 1, instruction: 12, function_name: Some(\"create_room\") }, 3)"
+    ));
+    assert!(!is_congestion_error("InsufficientGas"));
+}
@@ -0,0 +1,116 @@
+//! End-to-end test of `create_room` on a local network.
+//!
+//! Run with `cargo test --test create_room -- --ignored` once a local network is up and the
+//! `gamecards` package is available (see `common::gamecards_package`). The card passed to
+//! `create_room` is minted by calling `gamecards::<GAMECARDS_MINT_FUNCTION>` (default
+//! `mint_card`), or taken from `GAMECARDS_CARD_ID`.
+
+mod common;
+
+use std::env;
+
+use sui_connect::{
+    game::Deployment,
+    transcript::Transcript,
+    utils::{setup_for_write, Network},
+    Action, GameClient,
+};
+use sui_json_rpc_types::{ObjectChange, SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_sdk::types::{base_types::ObjectID, object::Owner};
+
+use common::{mint_card, GAS};
+
+#[tokio::test]
+#[ignore = "needs a local network with the gamecards package"]
+async fn create_room_shares_a_room() -> Result<(), anyhow::Error> {
+    let published = common::gamecards_package()?;
+    let (sui, sender, _) = setup_for_write(Network::Localnet).await?;
+    let deployment = Deployment {
+        package: published.package,
+        room: published.room,
+        card_type: None,
+        room_type: None,
+        room_ref: None,
+    };
+    let client = GameClient::new(sui, sender, deployment, GAS);
+
+    let card = mint_card(&client).await?;
+    let plan = client
+        .plan(&Action::CreateRoom {
+            card,
+            extra_objects: vec![],
+            room_owner: None,
+        })
+        .await?;
+    let response = client.execute_plan(plan).await?;
+
+    let effects = response.effects.expect("full content was requested");
+    assert_eq!(effects.status(), &SuiExecutionStatus::Success);
+
+    // The new room is a shared object whose type comes from the `gamecards` module
+    let rooms: Vec<_> = response
+        .object_changes
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|change| match change {
+            ObjectChange::Created {
+                object_type, owner, ..
+            } if matches!(owner, Owner::Shared { .. }) => Some(object_type),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        rooms.len(),
+        1,
+        "expected exactly one shared room, got {rooms:?}"
+    );
+    assert_eq!(ObjectID::from(rooms[0].address), published.package);
+    assert_eq!(rooms[0].module.as_str(), "gamecards");
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a local network with the gamecards package"]
+async fn a_plan_records_the_calls_of_the_helpers_it_uses() -> Result<(), anyhow::Error> {
+    let published = common::gamecards_package()?;
+    let (sui, sender, _) = setup_for_write(Network::Localnet).await?;
+    let deployment = Deployment {
+        package: published.package,
+        room: published.room,
+        card_type: None,
+        room_type: None,
+        room_ref: None,
+    };
+    let path = env::temp_dir().join(format!("plan-transcript-{}.jsonl", std::process::id()));
+    let client = GameClient::new(sui, sender, deployment, GAS)
+        .with_transcript(Some(Transcript::create(&path)?));
+
+    let card = mint_card(&client).await?;
+    client
+        .plan(&Action::CreateRoom {
+            card,
+            extra_objects: vec![],
+            room_owner: None,
+        })
+        .await?;
+
+    let contents = std::fs::read_to_string(&path)?;
+    std::fs::remove_file(&path)?;
+    let entries: Vec<serde_json::Value> = contents
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    for method in [
+        "sui_getProtocolConfig",
+        "sui_getObject",
+        "sui_getNormalizedMoveModulesByPackage",
+        "suix_getCoins",
+        "suix_getReferenceGasPrice",
+    ] {
+        assert!(
+            entries.iter().any(|entry| entry["method"] == method),
+            "{method} is missing from the transcript"
+        );
+    }
+    Ok(())
+}
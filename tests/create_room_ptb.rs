@@ -0,0 +1,99 @@
+//! Transfers of the objects returned by `create_room`, in a transaction built offline.
+
+use move_core_types::language_storage::TypeTag;
+use sui_connect::game::{self, Deployment};
+use sui_sdk::types::{
+    base_types::{ObjectID, SequenceNumber, SuiAddress},
+    digests::ObjectDigest,
+    parse_sui_struct_tag,
+    transaction::{Argument, CallArg, Command, ObjectArg, ProgrammableTransaction},
+};
+
+fn owned(byte: u8) -> ObjectArg {
+    ObjectArg::ImmOrOwnedObject((
+        ObjectID::from_single_byte(byte),
+        SequenceNumber::from_u64(1),
+        ObjectDigest::new([byte; 32]),
+    ))
+}
+
+/// The recipient of each `TransferObjects` command with the returned values it transfers.
+fn transfers(pt: &ProgrammableTransaction) -> Result<Vec<(SuiAddress, Vec<u16>)>, anyhow::Error> {
+    let mut transfers = vec![];
+    for command in &pt.commands {
+        let Command::TransferObjects(values, Argument::Input(address)) = command else {
+            continue;
+        };
+        let CallArg::Pure(bytes) = &pt.inputs[usize::from(*address)] else {
+            anyhow::bail!("The recipient of a transfer is not a pure input");
+        };
+        let returned = values
+            .iter()
+            .filter_map(|value| match value {
+                Argument::NestedResult(_, i) => Some(*i),
+                _ => None,
+            })
+            .collect();
+        transfers.push((bcs::from_bytes(bytes)?, returned));
+    }
+    Ok(transfers)
+}
+
+/// The `create_room` transaction of a placeholder deployment.
+fn build(returned: &[(u16, SuiAddress)]) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let deployment = Deployment {
+        package: ObjectID::from_single_byte(1),
+        room: ObjectID::from_single_byte(2),
+        card_type: None,
+        room_type: None,
+        room_ref: None,
+    };
+    let room = ObjectArg::SharedObject {
+        id: deployment.room,
+        initial_shared_version: SequenceNumber::from_u64(1),
+        mutable: true,
+    };
+    Ok(game::build_create_room(&deployment, room, owned(3), &[], returned)?.finish())
+}
+
+#[test]
+fn the_room_and_the_capabilities_go_to_their_own_recipients() -> Result<(), anyhow::Error> {
+    let sender = SuiAddress::random_for_testing_only();
+    let room_owner = SuiAddress::random_for_testing_only();
+    let pt = build(&[(0, room_owner), (1, sender), (2, sender)])?;
+
+    let mut expected = vec![(room_owner, vec![0]), (sender, vec![1, 2])];
+    expected.sort();
+    let mut transfers = transfers(&pt)?;
+    transfers.sort();
+    assert_eq!(transfers, expected);
+    Ok(())
+}
+
+#[test]
+fn nothing_is_transferred_when_nothing_is_returned() -> Result<(), anyhow::Error> {
+    assert!(transfers(&build(&[])?)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn only_objects_of_the_room_type_go_to_the_room_owner() -> Result<(), anyhow::Error> {
+    let sender = SuiAddress::random_for_testing_only();
+    let room_owner = SuiAddress::random_for_testing_only();
+    let room_type = parse_sui_struct_tag("0x1::gamecards::Table")?;
+    let capability = parse_sui_struct_tag("0x1::gamecards::RoomAdminRoom")?;
+    let returned = [
+        (0, TypeTag::Struct(Box::new(room_type.clone()))),
+        (1, TypeTag::Struct(Box::new(capability))),
+    ];
+
+    assert_eq!(
+        game::returned_recipients(&returned, &room_type, sender, Some(room_owner)),
+        [(0, room_owner), (1, sender)]
+    );
+    assert_eq!(
+        game::returned_recipients(&returned, &room_type, sender, None),
+        [(0, sender), (1, sender)]
+    );
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Gas budgets estimated from dry-run summaries, the configured budget limit, and batch
+//! projections.
+
+use sui_connect::{
+    batch::{BatchEstimate, OperationEstimate},
+    gas::{self, EstimateBasis},
+};
+use sui_sdk::types::{base_types::ObjectID, gas::GasCostSummary};
+
+const STORING: GasCostSummary = GasCostSummary {
+    computation_cost: 1_000,
+    storage_cost: 3_000,
+    storage_rebate: 500,
+    non_refundable_storage_fee: 10,
+};
+
+const FREEING: GasCostSummary = GasCostSummary {
+    computation_cost: 1_000,
+    storage_cost: 0,
+    storage_rebate: 2_500,
+    non_refundable_storage_fee: 10,
+};
+
+#[test]
+fn total_covers_the_net_cost_but_never_less_than_computation() {
+    assert_eq!(EstimateBasis::Total.budget(&STORING), 4_200);
+    assert_eq!(EstimateBasis::Total.budget(&FREEING), 1_200);
+}
+
+#[test]
+fn computation_ignores_storage() {
+    assert_eq!(EstimateBasis::Computation.budget(&STORING), 1_200);
+    assert_eq!(EstimateBasis::Computation.budget(&FREEING), 1_200);
+}
+
+#[test]
+fn computation_plus_storage_ignores_the_rebate() {
+    assert_eq!(
+        EstimateBasis::ComputationPlusStorage.budget(&STORING),
+        4_800
+    );
+    assert_eq!(
+        EstimateBasis::ComputationPlusStorage.budget(&FREEING),
+        1_200
+    );
+}
+
+#[test]
+fn the_buffer_rounds_up() {
+    let summary = GasCostSummary::new(1_001, 0, 0, 0);
+    assert_eq!(EstimateBasis::Computation.budget(&summary), 1_202);
+}
+
+#[test]
+fn budgets_above_the_limit_need_to_be_allowed() {
+    assert!(gas::check_budget(4_200, 5_000, false).is_ok());
+    assert!(gas::check_budget(4_200, 4_000, false).is_err());
+    assert!(gas::check_budget(4_200, 4_000, true).is_ok());
+}
+
+fn dry_run(index: usize, net_cost: i64, error: Option<&str>) -> OperationEstimate {
+    OperationEstimate {
+        index,
+        card: ObjectID::from_single_byte(index as u8),
+        net_cost,
+        error: error.map(str::to_string),
+    }
+}
+
+#[test]
+fn a_full_batch_projects_the_sum_of_its_dry_runs() {
+    let estimate = BatchEstimate {
+        operations: 2,
+        dry_runs: vec![dry_run(0, 1_000, None), dry_run(1, 1_500, Some("abort"))],
+    };
+    assert!(!estimate.is_sampled());
+    assert_eq!(estimate.projected_total(), 2_500);
+    assert_eq!(estimate.failures(), 1);
+}
+
+#[test]
+fn a_sample_is_extrapolated_to_the_whole_batch() {
+    let estimate = BatchEstimate {
+        operations: 10,
+        dry_runs: vec![dry_run(0, 1_000, None), dry_run(5, 2_001, None)],
+    };
+    assert!(estimate.is_sampled());
+    assert_eq!(estimate.average_net_cost(), Some(1_500));
+    assert_eq!(estimate.projected_total(), 15_000);
+    assert_eq!(estimate.failures(), 0);
+}
+
+#[test]
+fn an_empty_batch_costs_nothing() {
+    let estimate = BatchEstimate {
+        operations: 0,
+        dry_runs: vec![],
+    };
+    assert_eq!(estimate.average_net_cost(), None);
+    assert_eq!(estimate.projected_total(), 0);
+}
@@ -0,0 +1,53 @@
+//! A gas pool picks up changes made to its coins by other clients.
+//!
+//! Run with `cargo test --test gas_pool -- --ignored` once a local network is up and the
+//! active address has SUI.
+
+mod common;
+
+use std::time::Duration;
+
+use sui_connect::{
+    game::{self, Deployment},
+    gas_pool::GasPool,
+    utils::{setup_for_write, Network},
+    Action, GameClient,
+};
+use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+
+use common::GAS;
+
+#[tokio::test]
+#[ignore = "needs a local network"]
+async fn the_next_plan_uses_the_refreshed_gas_coin() -> Result<(), anyhow::Error> {
+    let (sui, sender, recipient) = setup_for_write(Network::Localnet).await?;
+    let transfer = Action::TransferSui {
+        recipient,
+        amount: 1_000,
+    };
+
+    // The pool manages the coin that a client without a pool picks for the same transfer
+    let coin = game::gas_coin(&sui, sender, GAS.budget, None).await?;
+    let pool = GasPool::new(&sui, sender, vec![coin.0], None).await?;
+    let refresh = pool.spawn_refresh(sui.clone(), Duration::from_millis(200));
+    let pooled = GameClient::new(sui.clone(), sender, Deployment::default(), GAS)
+        .with_gas_pool(pool.clone());
+
+    // Another client uses the coin, which gives it a new version
+    let other = GameClient::new(sui.clone(), sender, Deployment::default(), GAS);
+    let plan = other.plan(&transfer).await?;
+    assert_eq!(plan.gas_coin.0, coin.0);
+    let response = other.execute_plan(plan).await?;
+    let effects = response.effects.expect("full content was requested");
+    assert_eq!(effects.status(), &SuiExecutionStatus::Success);
+    let mutated = effects.gas_object().reference.to_object_ref();
+    assert!(mutated.1 > coin.1);
+
+    // Once the background task has refreshed the pool, plans use the new ref
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let plan = pooled.plan(&transfer).await?;
+    assert_eq!(plan.gas_coin, mutated);
+
+    refresh.cancel();
+    Ok(())
+}
@@ -0,0 +1,25 @@
+//! Aggregation of gas summaries into a gas report.
+
+use sui_connect::gas::GasReport;
+use sui_sdk::types::gas::GasCostSummary;
+
+#[test]
+fn totals_are_net_of_rebates_and_skip_missing_effects() {
+    let mut report = GasReport::default();
+    report.add(Some(&GasCostSummary::new(1_000, 3_000, 500, 10)));
+    report.add(None);
+    report.add(Some(&GasCostSummary::new(1_000, 0, 2_500, 10)));
+
+    assert_eq!(report.transactions, 2);
+    assert_eq!(report.missing_effects, 1);
+    assert_eq!(report.computation_cost, 2_000);
+    assert_eq!(report.storage_cost, 3_000);
+    assert_eq!(report.storage_rebate, 3_000);
+    assert_eq!(report.net_cost(), 2_000);
+    assert_eq!(report.average_net_cost(), Some(1_000));
+}
+
+#[test]
+fn an_empty_report_has_no_average() {
+    assert_eq!(GasReport::default().average_net_cost(), None);
+}
@@ -0,0 +1,32 @@
+//! Which keystore errors the signing step retries.
+
+use std::io;
+
+use anyhow::anyhow;
+use sui_connect::game::is_transient_keystore_error;
+use sui_sdk::types::base_types::SuiAddress;
+
+#[test]
+fn a_missing_keystore_file_is_not_retried() {
+    let error = anyhow::Error::from(io::Error::from(io::ErrorKind::NotFound))
+        .context("Cannot open the keystore");
+    assert!(!is_transient_keystore_error(&error));
+}
+
+#[test]
+fn a_missing_key_is_not_retried() {
+    let sender = SuiAddress::random_for_testing_only();
+    let error = anyhow!("Cannot find key for address: [{sender}]");
+    assert!(!is_transient_keystore_error(&error));
+}
+
+#[test]
+fn a_keystore_file_being_rewritten_is_retried() {
+    // A file read halfway through a rewrite is truncated JSON
+    let parse_error = serde_json::from_str::<Vec<String>>("[\"AQ").unwrap_err();
+    let error = anyhow::Error::from(parse_error).context("Cannot read the keystore");
+    assert!(is_transient_keystore_error(&error));
+
+    let error = anyhow::Error::from(io::Error::from(io::ErrorKind::PermissionDenied));
+    assert!(is_transient_keystore_error(&error));
+}
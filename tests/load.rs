@@ -0,0 +1,35 @@
+//! Statistics of a load test report.
+
+use std::time::Duration;
+
+use sui_connect::load::{LoadReport, Sample};
+
+fn sample(millis: u64, success: bool) -> Sample {
+    Sample {
+        latency: Duration::from_millis(millis),
+        success,
+    }
+}
+
+#[test]
+fn rates_and_percentiles_cover_every_transaction() {
+    let samples: Vec<_> = (1..=10).map(|i| sample(i * 100, i != 5)).collect();
+    let report = LoadReport::new(&samples, Duration::from_secs(3));
+
+    assert_eq!(report.submitted, 10);
+    assert_eq!(report.succeeded, 9);
+    assert_eq!(report.achieved_tps(), 3.0);
+    assert_eq!(report.success_rate(), 0.9);
+    assert_eq!(report.percentile(50.0), Some(Duration::from_millis(500)));
+    assert_eq!(report.percentile(90.0), Some(Duration::from_millis(900)));
+    assert_eq!(report.percentile(100.0), Some(Duration::from_millis(1_000)));
+    assert_eq!(report.percentile(0.0), Some(Duration::from_millis(100)));
+}
+
+#[test]
+fn an_empty_report_has_no_latencies() {
+    let report = LoadReport::new(&[], Duration::ZERO);
+    assert_eq!(report.percentile(50.0), None);
+    assert_eq!(report.achieved_tps(), 0.0);
+    assert_eq!(report.success_rate(), 0.0);
+}
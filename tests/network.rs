@@ -0,0 +1,23 @@
+//! WebSocket URLs derived from RPC URLs.
+
+use sui_connect::utils::{self, Network};
+
+#[test]
+fn the_websocket_url_keeps_the_host_of_the_rpc_url() {
+    assert_eq!(
+        utils::ws_url("https://rpc.example.com:443/v1"),
+        "wss://rpc.example.com:443/v1"
+    );
+    assert_eq!(
+        utils::ws_url("http://127.0.0.1:9000"),
+        "ws://127.0.0.1:9000"
+    );
+}
+
+#[test]
+fn public_full_nodes_serve_subscriptions_on_the_rpc_host() {
+    assert_eq!(
+        utils::ws_url(Network::Mainnet.rpc_url()),
+        "wss://fullnode.mainnet.sui.io:443"
+    );
+}
@@ -0,0 +1,46 @@
+//! What a plan takes out of its gas coin besides gas, and the objects it uses.
+
+use sui_connect::{game, TransactionPlan};
+use sui_sdk::types::{
+    base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress},
+    digests::ObjectDigest,
+};
+
+fn coin(byte: u8) -> ObjectRef {
+    (
+        ObjectID::from_single_byte(byte),
+        SequenceNumber::from_u64(1),
+        ObjectDigest::new([byte; 32]),
+    )
+}
+
+fn plan(source: &[ObjectRef]) -> Result<TransactionPlan, anyhow::Error> {
+    let pt = game::transfer_sui_ptb(SuiAddress::random_for_testing_only(), 1_500, source)?;
+    Ok(TransactionPlan::new(
+        SuiAddress::random_for_testing_only(),
+        pt,
+        coin(1),
+        10_000_000,
+        1_000,
+    ))
+}
+
+#[test]
+fn a_transfer_paid_from_the_gas_coin_is_split_from_it() -> Result<(), anyhow::Error> {
+    assert_eq!(plan(&[])?.split_from_gas(), 1_500);
+    Ok(())
+}
+
+#[test]
+fn a_transfer_from_other_coins_leaves_the_gas_coin_alone() -> Result<(), anyhow::Error> {
+    assert_eq!(plan(&[coin(2), coin(3)])?.split_from_gas(), 0);
+    Ok(())
+}
+
+#[test]
+fn the_coins_a_transfer_is_paid_from_are_used_by_the_plan() -> Result<(), anyhow::Error> {
+    let plan = plan(&[coin(2), coin(3)])?;
+    assert!(plan.uses_object(coin(2).0));
+    assert!(!plan.uses_object(coin(1).0));
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! Input deduplication of `PtbBuilder`.
+
+use sui_connect::ptb::PtbBuilder;
+use sui_sdk::types::{
+    base_types::{ObjectID, SequenceNumber},
+    digests::ObjectDigest,
+    transaction::{CallArg, Command, ObjectArg},
+};
+
+fn owned_object(version: u64) -> ObjectArg {
+    ObjectArg::ImmOrOwnedObject((
+        ObjectID::from_single_byte(7),
+        SequenceNumber::from_u64(version),
+        ObjectDigest::new([7; 32]),
+    ))
+}
+
+#[test]
+fn an_object_used_by_two_commands_is_a_single_input() -> Result<(), anyhow::Error> {
+    let mut ptb = PtbBuilder::new();
+    let first = ptb.object(owned_object(3))?;
+    ptb.command(Command::MakeMoveVec(None, vec![first]));
+    let second = ptb.object(owned_object(3))?;
+    ptb.command(Command::MakeMoveVec(None, vec![second]));
+
+    assert_eq!(first, second);
+    let pt = ptb.finish();
+    assert_eq!(pt.inputs.len(), 1);
+    assert_eq!(pt.commands.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn an_object_at_two_versions_is_rejected() -> Result<(), anyhow::Error> {
+    let mut ptb = PtbBuilder::new();
+    ptb.object(owned_object(3))?;
+    assert!(ptb.object(owned_object(4)).is_err());
+    Ok(())
+}
+
+#[test]
+fn a_shared_object_used_mutably_once_is_a_mutable_input() -> Result<(), anyhow::Error> {
+    let shared = |mutable| ObjectArg::SharedObject {
+        id: ObjectID::from_single_byte(9),
+        initial_shared_version: SequenceNumber::from_u64(1),
+        mutable,
+    };
+    let mut ptb = PtbBuilder::new();
+    let first = ptb.object(shared(false))?;
+    let second = ptb.object(shared(true))?;
+
+    assert_eq!(first, second);
+    let pt = ptb.finish();
+    assert_eq!(pt.inputs.len(), 1);
+    assert!(matches!(
+        pt.inputs[0],
+        CallArg::Object(ObjectArg::SharedObject { mutable: true, .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn without_dedup_a_repeated_object_is_rejected() -> Result<(), anyhow::Error> {
+    let mut ptb = PtbBuilder::new().with_dedup(false);
+    ptb.object(owned_object(3))?;
+    assert!(ptb.object(owned_object(3)).is_err());
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! Which errors the retry policies retry.
+
+use std::cell::Cell;
+
+use sui_connect::{
+    error::GameError,
+    retry::{self, RetryPolicy, TimedOut},
+};
+
+const QUICK: RetryPolicy = RetryPolicy {
+    attempts: 3,
+    timeout_secs: 5,
+    backoff_ms: 1,
+};
+
+#[tokio::test]
+async fn timeouts_are_retried() {
+    let attempts = Cell::new(0);
+    let result = QUICK
+        .run(|| {
+            attempts.set(attempts.get() + 1);
+            async { Err::<(), _>(TimedOut { secs: 5 }.into()) }
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.get(), QUICK.attempts);
+}
+
+#[tokio::test]
+async fn answers_from_the_full_node_are_not_retried() {
+    let attempts = Cell::new(0);
+    let result = QUICK
+        .run(|| {
+            attempts.set(attempts.get() + 1);
+            async {
+                Err::<(), _>(
+                    GameError::WrongObjectType {
+                        id: sui_sdk::types::base_types::ObjectID::ZERO,
+                        expected: "0x7::gamecards::Card".to_string(),
+                        found: "0x2::coin::Coin<0x2::sui::SUI>".to_string(),
+                    }
+                    .into(),
+                )
+            }
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.get(), 1);
+}
+
+#[test]
+fn context_does_not_hide_a_transient_cause() {
+    let error = anyhow::Error::from(TimedOut { secs: 30 }).context("Cannot fetch the room");
+    assert!(retry::is_transient(&error));
+    assert!(!retry::is_transient(&anyhow::anyhow!(
+        "Object 0x7 does not exist"
+    )));
+}
@@ -0,0 +1,41 @@
+//! Parsing of scripts and substitution of bindings.
+
+use sui_connect::script::{self, Bindings};
+use sui_sdk::types::base_types::ObjectID;
+
+#[test]
+fn bindings_comments_and_quotes_are_parsed() -> Result<(), anyhow::Error> {
+    let lines = script::parse(
+        "# Create a room and look at it\n\
+         \n\
+         room = create-room --card 0x7\n\
+         show-object --id $room\n\
+         encode-arg --type 0x1::string::String --value 'two words'\n",
+    )?;
+
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0].number, 3);
+    assert_eq!(lines[0].binding.as_deref(), Some("room"));
+    assert_eq!(lines[0].words, ["create-room", "--card", "0x7"]);
+    assert_eq!(lines[1].binding, None);
+    assert_eq!(lines[2].words.last().map(String::as_str), Some("two words"));
+    Ok(())
+}
+
+#[test]
+fn bound_ids_are_substituted_by_index() -> Result<(), anyhow::Error> {
+    let first = ObjectID::from_single_byte(1);
+    let second = ObjectID::from_single_byte(2);
+    let mut bindings = Bindings::default();
+    bindings.bind("rooms".to_string(), vec![first, second]);
+
+    assert_eq!(bindings.substitute("$rooms")?, first.to_string());
+    assert_eq!(bindings.substitute("$rooms.1")?, second.to_string());
+    assert_eq!(
+        bindings.substitute("--id=$rooms.0")?,
+        format!("--id={first}")
+    );
+    assert!(bindings.substitute("$rooms.2").is_err());
+    assert!(bindings.substitute("$cards").is_err());
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! Parsing and sizing of swept argument ranges, and sweeps on a local network.
+//!
+//! The sweep test needs a `gamecards` package whose `create_room` takes an integer after
+//! the room and the cards (see `common::gamecards_package`); run it with
+//! `cargo test --test sweep -- --ignored`.
+
+mod common;
+
+use sui_connect::{
+    game::Deployment,
+    gas::{EstimateBasis, GasSettings},
+    sweep::{self, ArgRange},
+    utils::{setup_for_write, Network},
+    GameClient,
+};
+
+use common::{mint_card, GAS};
+
+#[test]
+fn ranges_count_their_values() {
+    let excluded: ArgRange = "fee:1..10".parse().unwrap();
+    assert_eq!(excluded.len(), 9);
+    let included: ArgRange = "fee:1..=10".parse().unwrap();
+    assert_eq!(included.len(), 10);
+    let full: ArgRange = format!("fee:0..={}", u128::MAX).parse().unwrap();
+    assert_eq!(full.len(), u128::MAX);
+}
+
+#[test]
+fn empty_ranges_are_rejected() {
+    assert!("fee:5..5".parse::<ArgRange>().is_err());
+    assert!("fee:5..=4".parse::<ArgRange>().is_err());
+    let single: ArgRange = "fee:5..=5".parse().unwrap();
+    assert_eq!(single.len(), 1);
+}
+
+#[tokio::test]
+#[ignore = "needs a local network with a gamecards package whose create_room takes an integer"]
+async fn a_sweep_runs_with_an_estimate_basis() -> Result<(), anyhow::Error> {
+    let published = common::gamecards_package()?;
+    let (sui, sender, _) = setup_for_write(Network::Localnet).await?;
+    let deployment = Deployment {
+        package: published.package,
+        room: published.room,
+        ..Deployment::default()
+    };
+    let gas = GasSettings {
+        estimate_basis: Some(EstimateBasis::Total),
+        ..GAS
+    };
+    let client = GameClient::new(sui, sender, deployment, gas);
+
+    // The plan without the swept argument would fail its dry run, so it must not be estimated
+    let card = mint_card(&client).await?;
+    let range: ArgRange = "value:1..=3".parse()?;
+    let results = sweep::sweep_create_room(&client, card, &range, sweep::MAX_SWEEP_VALUES).await?;
+
+    let values: Vec<_> = results.iter().map(|result| result.value).collect();
+    assert_eq!(values, [1, 2, 3]);
+    Ok(())
+}
@@ -0,0 +1,20 @@
+//! Choice of the recipient among the wallet's addresses.
+
+use sui_connect::utils;
+use sui_sdk::types::base_types::SuiAddress;
+
+#[test]
+fn a_wallet_with_one_address_has_no_recipient() {
+    let sender = SuiAddress::random_for_testing_only();
+    let error = utils::other_address(sender, &[sender]).unwrap_err();
+    assert!(error.to_string().contains("second address"));
+    assert!(utils::other_address(sender, &[]).is_err());
+}
+
+#[test]
+fn the_recipient_is_another_address_than_the_sender() -> Result<(), anyhow::Error> {
+    let sender = SuiAddress::random_for_testing_only();
+    let other = SuiAddress::random_for_testing_only();
+    assert_eq!(utils::other_address(sender, &[sender, other])?, other);
+    Ok(())
+}